@@ -0,0 +1,226 @@
+//! User markers and annotations
+//!
+//! Annotations live in world space and are drawn through the `Camera` transform.
+//! The store round-trips through JSON so Flutter can persist and share them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Version of the exported JSON document
+const EXPORT_VERSION: u32 = 1;
+
+/// Extra pick radius around annotations, in CSS pixels
+const PICK_SLOP_PX: f64 = 4.0;
+
+/// Geometry of an annotation, in world coordinates
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Shape {
+    /// Pin at a single point with an icon glyph (e.g. an emoji)
+    Marker { x: f64, y: f64, icon: String },
+    /// Open line through the points; `width` in CSS pixels
+    Polyline { points: Vec<(f64, f64)>, width: f64 },
+    /// Closed, filled polygon
+    Area { points: Vec<(f64, f64)> },
+}
+
+impl Shape {
+    /// Whether every coordinate and width is finite; JSON cannot carry the rest
+    pub fn is_finite(&self) -> bool {
+        match self {
+            Shape::Marker { x, y, .. } => x.is_finite() && y.is_finite(),
+            Shape::Polyline { points, width } => width.is_finite() && points_finite(points),
+            Shape::Area { points } => points_finite(points),
+        }
+    }
+
+    /// Check the shape can be stored: finite coordinates and a positive line width
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.is_finite() {
+            return Err("Annotation coordinates must be finite".to_string());
+        }
+        match self {
+            Shape::Polyline { width, .. } if *width <= 0.0 => {
+                Err(format!("Polyline width must be positive, not {}", width))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub id: u32,
+    /// Packed 0xRRGGBB
+    pub color: u32,
+    #[serde(default)]
+    pub label: String,
+    #[serde(flatten)]
+    pub shape: Shape,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AnnotationDocument {
+    version: u32,
+    annotations: Vec<Annotation>,
+}
+
+/// Annotations in insertion order (later ones draw on top)
+pub struct AnnotationStore {
+    annotations: Vec<Annotation>,
+    /// None once id `u32::MAX` has been taken
+    next_id: Option<u32>,
+}
+
+impl Default for AnnotationStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnnotationStore {
+    pub fn new() -> Self {
+        Self {
+            annotations: Vec::new(),
+            next_id: Some(1),
+        }
+    }
+
+    /// Add an annotation and return its id
+    pub fn add(&mut self, color: u32, label: String, shape: Shape) -> Result<u32, String> {
+        shape.validate()?;
+        let id = self.next_id.ok_or("No annotation ids left")?;
+        self.next_id = id.checked_add(1);
+        self.annotations.push(Annotation {
+            id,
+            color,
+            label,
+            shape,
+        });
+        Ok(id)
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut Annotation> {
+        self.annotations.iter_mut().find(|a| a.id == id)
+    }
+
+    pub fn remove(&mut self, id: u32) -> bool {
+        let before = self.annotations.len();
+        self.annotations.retain(|a| a.id != id);
+        self.annotations.len() != before
+    }
+
+    pub fn clear(&mut self) {
+        self.annotations.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        self.annotations.iter()
+    }
+
+    /// Topmost annotation under a world-space point.
+    /// `world_per_px` converts the pixel-sized marker radius and line widths to world units.
+    pub fn hit_test(&self, x: f64, y: f64, world_per_px: f64, marker_radius_px: f64) -> Option<u32> {
+        let slop = PICK_SLOP_PX * world_per_px;
+        self.annotations
+            .iter()
+            .rev()
+            .find(|a| match &a.shape {
+                Shape::Marker { x: mx, y: my, .. } => {
+                    (x - mx).hypot(y - my) <= marker_radius_px * world_per_px + slop
+                }
+                Shape::Polyline { points, width } => {
+                    distance_to_path(points, false, x, y) <= width / 2.0 * world_per_px + slop
+                }
+                Shape::Area { points } => {
+                    point_in_polygon(points, x, y) || distance_to_path(points, true, x, y) <= slop
+                }
+            })
+            .map(|a| a.id)
+    }
+
+    pub fn to_json(&self) -> String {
+        let doc = AnnotationDocument {
+            version: EXPORT_VERSION,
+            annotations: self.annotations.clone(),
+        };
+        // Plain data: serialization cannot fail
+        serde_json::to_string(&doc).unwrap()
+    }
+
+    /// Replace the store with annotations from JSON produced by `to_json`
+    pub fn load_json(&mut self, json: &str) -> Result<(), String> {
+        let doc: AnnotationDocument =
+            serde_json::from_str(json).map_err(|e| format!("Invalid annotation JSON: {}", e))?;
+        if doc.version > EXPORT_VERSION {
+            return Err(format!("Unsupported annotation version: {}", doc.version));
+        }
+        let mut ids = HashSet::new();
+        for a in &doc.annotations {
+            if !ids.insert(a.id) {
+                return Err(format!("Duplicate annotation id: {}", a.id));
+            }
+            a.shape
+                .validate()
+                .map_err(|e| format!("Annotation {}: {}", a.id, e))?;
+        }
+        // Ids continue after the highest imported one, if any are left
+        self.next_id = match doc.annotations.iter().map(|a| a.id).max() {
+            Some(max) => max.checked_add(1),
+            None => Some(1),
+        };
+        self.annotations = doc.annotations;
+        Ok(())
+    }
+}
+
+/// Unpack a flat [x1, y1, x2, y2, ...] list into points
+pub fn points_from_flat(flat: &[f64]) -> Result<Vec<(f64, f64)>, String> {
+    if !flat.len().is_multiple_of(2) {
+        return Err(format!("Points need an even number of coordinates, not {}", flat.len()));
+    }
+    Ok(flat.chunks_exact(2).map(|p| (p[0], p[1])).collect())
+}
+
+/// Whether every coordinate is finite
+pub fn points_finite(points: &[(f64, f64)]) -> bool {
+    points.iter().all(|p| p.0.is_finite() && p.1.is_finite())
+}
+
+fn distance_to_segment(px: f64, py: f64, a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq > 0.0 {
+        (((px - a.0) * dx + (py - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (px - (a.0 + t * dx)).hypot(py - (a.1 + t * dy))
+}
+
+fn distance_to_path(points: &[(f64, f64)], closed: bool, x: f64, y: f64) -> f64 {
+    match points.len() {
+        0 => f64::INFINITY,
+        1 => (x - points[0].0).hypot(y - points[0].1),
+        n => {
+            let segments = if closed { n } else { n - 1 };
+            (0..segments)
+                .map(|i| distance_to_segment(x, y, points[i], points[(i + 1) % n]))
+                .fold(f64::INFINITY, f64::min)
+        }
+    }
+}
+
+/// Even-odd ray casting
+fn point_in_polygon(points: &[(f64, f64)], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    let n = points.len();
+    for i in 0..n {
+        let (xi, yi) = points[i];
+        let (xj, yj) = points[(i + n - 1) % n];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+    }
+    inside
+}
@@ -0,0 +1,145 @@
+//! Ordered, toggleable render layers
+//!
+//! Layers are drawn bottom to top in registry order. Flutter addresses them by name.
+
+/// Built-in layers
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Layer {
+    Background,
+    Terrain,
+    Rivers,
+    Grid,
+    Roads,
+    Borders,
+    Cities,
+    Trails,
+    Salesmen,
+    Overlays,
+    Debug,
+}
+
+impl Layer {
+    /// All built-in layers in default draw order
+    pub const ALL: [Layer; 11] = [
+        Layer::Background,
+        Layer::Terrain,
+        Layer::Rivers,
+        Layer::Grid,
+        Layer::Roads,
+        Layer::Borders,
+        Layer::Cities,
+        Layer::Trails,
+        Layer::Salesmen,
+        Layer::Overlays,
+        Layer::Debug,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Layer::Background => "background",
+            Layer::Terrain => "terrain",
+            Layer::Rivers => "rivers",
+            Layer::Grid => "grid",
+            Layer::Roads => "roads",
+            Layer::Borders => "borders",
+            Layer::Cities => "cities",
+            Layer::Trails => "trails",
+            Layer::Salesmen => "salesmen",
+            Layer::Overlays => "overlays",
+            Layer::Debug => "debug",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Layer> {
+        Self::ALL.iter().copied().find(|l| l.name() == name)
+    }
+}
+
+/// Per-layer render settings
+#[derive(Clone, Copy, Debug)]
+pub struct LayerState {
+    pub layer: Layer,
+    pub enabled: bool,
+    /// Global alpha applied while drawing the layer (0.0-1.0)
+    pub opacity: f64,
+}
+
+/// Registry of layers in draw order
+#[derive(Clone, Debug)]
+pub struct LayerStack {
+    layers: Vec<LayerState>,
+}
+
+impl Default for LayerStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayerStack {
+    /// Default stack: everything enabled except debug
+    pub fn new() -> Self {
+        let layers = Layer::ALL
+            .iter()
+            .map(|&layer| LayerState {
+                layer,
+                enabled: layer != Layer::Debug,
+                opacity: 1.0,
+            })
+            .collect();
+        Self { layers }
+    }
+
+    fn state_mut(&mut self, layer: Layer) -> &mut LayerState {
+        // Every built-in layer is always present
+        self.layers.iter_mut().find(|s| s.layer == layer).unwrap()
+    }
+
+    pub fn state(&self, layer: Layer) -> LayerState {
+        *self.layers.iter().find(|s| s.layer == layer).unwrap()
+    }
+
+    pub fn set_enabled(&mut self, layer: Layer, enabled: bool) {
+        self.state_mut(layer).enabled = enabled;
+    }
+
+    pub fn set_opacity(&mut self, layer: Layer, opacity: f64) {
+        self.state_mut(layer).opacity = if opacity.is_nan() { 1.0 } else { opacity.clamp(0.0, 1.0) };
+    }
+
+    /// Reorder layers. Layers listed in `order` are moved to the front in that
+    /// order; unlisted layers keep their relative order after them.
+    pub fn set_order(&mut self, order: &[Layer]) {
+        let mut reordered = Vec::with_capacity(self.layers.len());
+        for layer in order {
+            if !reordered.iter().any(|s: &LayerState| s.layer == *layer) {
+                reordered.push(self.state(*layer));
+            }
+        }
+        for state in &self.layers {
+            if !order.contains(&state.layer) {
+                reordered.push(*state);
+            }
+        }
+        self.layers = reordered;
+    }
+
+    /// Layers in draw order
+    pub fn order(&self) -> impl Iterator<Item = Layer> + '_ {
+        self.layers.iter().map(|s| s.layer)
+    }
+
+    /// Enabled layers with non-zero opacity, in draw order
+    pub fn visible(&self) -> Vec<LayerState> {
+        self.layers
+            .iter()
+            .filter(|s| s.enabled && s.opacity > 0.0)
+            .copied()
+            .collect()
+    }
+
+    pub fn is_visible(&self, layer: Layer) -> bool {
+        let state = self.state(layer);
+        state.enabled && state.opacity > 0.0
+    }
+}
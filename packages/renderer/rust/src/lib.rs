@@ -2,10 +2,24 @@
 //!
 //! Uses Canvas2D for initial implementation, with path to upgrade to WebGPU.
 
+mod annotations;
+mod layers;
+mod theme;
+
 use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
-use web_sys::{HtmlCanvasElement, CanvasRenderingContext2d};
-use opengrid_world::{ChunkCache, ChunkCoord, CHUNK_SIZE, Camera};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::{Clamped, JsCast};
+use web_sys::{HtmlCanvasElement, CanvasRenderingContext2d, ImageData};
+use opengrid_world::{
+    roads_touching_chunk, territories_for_chunk, Border, ChunkCache, ChunkCoord, PlacementStrategy,
+    RegionCoord, RegionData, Road, TerrainGrid, Camera, WorldConfig, WorldSeed,
+};
+
+pub use theme::Theme;
+pub use layers::{Layer, LayerState, LayerStack};
+pub use annotations::{Annotation, AnnotationStore, Shape};
+use annotations::{points_finite, points_from_flat};
+use theme::rgb;
 
 /// City info returned to Flutter on click
 #[wasm_bindgen]
@@ -90,14 +104,40 @@ pub struct WorldRenderer {
     ctx: CanvasRenderingContext2d,
     camera: Camera,
     chunks: ChunkCache,
+    /// Configured cache capacity; frames that touch more chunks raise the
+    /// cache's capacity above it
+    cache_capacity: usize,
+    theme: Theme,
+    layers: LayerStack,
+    annotations: AnnotationStore,
+    
+    // Terrain layer: one offscreen canvas per chunk, one pixel per cell
+    terrain_tiles: HashMap<ChunkCoord, HtmlCanvasElement>,
+    
+    // Roads layer: roads touching each chunk (shared roads appear in both chunks)
+    chunk_roads: HashMap<ChunkCoord, Vec<Road>>,
+    
+    // Borders layer: territory and national borders owned by each chunk
+    chunk_borders: HashMap<ChunkCoord, Vec<Border>>,
+    world_seed: u64,
+    
+    // Cities layer: macro structure of regions in view (capitals, trade hubs)
+    regions: HashMap<RegionCoord, RegionData>,
     
     // Path-based salesman animation
     salesman_paths: Vec<SalesmanPath>,
     animation_start_time: f64,
     
+    // HiDPI: backing store is CSS size * pixel_ratio
+    pixel_ratio: f64,
+    max_pixel_ratio: f64,
+    
     // Animation state
     running: bool,
     
+    // Debug layer: recently evicted chunks and the frame they left the cache
+    recent_evictions: Vec<(ChunkCoord, u64)>,
+    
     // Stats state
     last_visible_chunks: u32,
     last_total_cities: u32,
@@ -107,11 +147,150 @@ fn get_time_seconds() -> f64 {
     js_sys::Date::now() / 1000.0
 }
 
+fn parse_layer(name: &str) -> Result<Layer, JsValue> {
+    Layer::from_name(name).ok_or_else(|| JsValue::from_str(&format!("Unknown layer: {}", name)))
+}
+
+/// Rasterize a chunk's biomes into an offscreen canvas, one pixel per cell
+fn build_terrain_tile(terrain: &TerrainGrid, theme: &Theme) -> Option<HtmlCanvasElement> {
+    let document = web_sys::window()?.document()?;
+    let tile = document
+        .create_element("canvas")
+        .ok()?
+        .dyn_into::<HtmlCanvasElement>()
+        .ok()?;
+    let size = terrain.size() as u32;
+    tile.set_width(size);
+    tile.set_height(size);
+    let tile_ctx = tile
+        .get_context("2d")
+        .ok()??
+        .dyn_into::<CanvasRenderingContext2d>()
+        .ok()?;
+    
+    let mut pixels = Vec::with_capacity(terrain.cells().len() * 4);
+    for biome in terrain.cells() {
+        let (r, g, b) = rgb(theme.biome_color(biome));
+        pixels.extend_from_slice(&[r as u8, g as u8, b as u8, 255]);
+    }
+    let image = ImageData::new_with_u8_clamped_array_and_sh(
+        Clamped(&pixels),
+        size,
+        size,
+    )
+    .ok()?;
+    tile_ctx.put_image_data(&image, 0.0, 0.0).ok()?;
+    Some(tile)
+}
+
+/// Maximum chunks whose roads are derived in one frame
+const ROAD_BUILDS_PER_FRAME: usize = 4;
+
+/// Maximum chunks whose territory borders are derived in one frame
+const BORDER_BUILDS_PER_FRAME: usize = 2;
+
+/// How long evicted chunks stay highlighted in the debug layer
+const EVICTION_HIGHLIGHT_FRAMES: u64 = 60;
+
+// Debug layer colours
+const DEBUG_FRESH_COLOR: &str = "rgba(255, 170, 0, 0.9)";
+const DEBUG_CACHED_COLOR: &str = "rgba(0, 200, 120, 0.6)";
+const DEBUG_EVICTED_COLOR: &str = "rgba(255, 60, 60, 0.9)";
+
+/// Default cap on the device pixel ratio used for the backing store
+const DEFAULT_MAX_PIXEL_RATIO: f64 = 3.0;
+
+/// Current device pixel ratio, clamped to [1.0, max]
+fn get_pixel_ratio(max: f64) -> f64 {
+    let dpr = web_sys::window()
+        .map(|w| w.device_pixel_ratio())
+        .filter(|r| r.is_finite() && *r > 0.0)
+        .unwrap_or(1.0);
+    dpr.clamp(1.0, max.max(1.0))
+}
+
+/// World configuration from JSON, as a JS error if invalid
+fn parse_config(json: &str) -> Result<WorldConfig, JsValue> {
+    WorldConfig::from_json(json).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Chunks a frame reads: the visible rectangle and the ring around it that
+/// roads and borders look into
+fn frame_chunk_count(visible: &[ChunkCoord]) -> usize {
+    let span = |axis: fn(&ChunkCoord) -> i32| {
+        let min = visible.iter().map(axis).min().unwrap_or(0) as i64;
+        let max = visible.iter().map(axis).max().unwrap_or(-1) as i64;
+        (max - min + 3) as usize
+    };
+    span(|c| c.x) * span(|c| c.y)
+}
+
 #[wasm_bindgen]
 impl WorldRenderer {
     /// Create new renderer attached to a canvas
     #[wasm_bindgen(constructor)]
     pub fn new(canvas: HtmlCanvasElement, world_seed: u32) -> Result<WorldRenderer, JsValue> {
+        Self::with_theme(canvas, world_seed, Theme::dark())
+    }
+    
+    /// Create new renderer with a custom theme
+    #[wasm_bindgen]
+    pub fn with_theme(
+        canvas: HtmlCanvasElement,
+        world_seed: u32,
+        theme: Theme,
+    ) -> Result<WorldRenderer, JsValue> {
+        Self::build(canvas, u64::from(world_seed), WorldConfig::default(), theme)
+    }
+    
+    /// Create new renderer for a JSON `WorldConfig`; missing fields take their
+    /// defaults. Terrain, rivers and regions need `generator_version` 2 or later.
+    #[wasm_bindgen]
+    pub fn with_config(
+        canvas: HtmlCanvasElement,
+        world_seed: u32,
+        config_json: &str,
+    ) -> Result<WorldRenderer, JsValue> {
+        let config = parse_config(config_json)?;
+        Self::build(canvas, u64::from(world_seed), config, Theme::dark())
+    }
+    
+    /// Create new renderer from a seed as players type it: a decimal number up
+    /// to 2^64 - 1, or a phrase
+    #[wasm_bindgen]
+    pub fn from_seed(canvas: HtmlCanvasElement, seed: &str) -> Result<WorldRenderer, JsValue> {
+        Self::from_seed_with_theme(canvas, seed, Theme::dark())
+    }
+    
+    /// `from_seed` with a custom theme
+    #[wasm_bindgen]
+    pub fn from_seed_with_theme(
+        canvas: HtmlCanvasElement,
+        seed: &str,
+        theme: Theme,
+    ) -> Result<WorldRenderer, JsValue> {
+        let seed = WorldSeed::parse(seed).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Self::build(canvas, seed.value(), WorldConfig::default(), theme)
+    }
+    
+    /// `from_seed` with a JSON `WorldConfig`
+    #[wasm_bindgen]
+    pub fn from_seed_with_config(
+        canvas: HtmlCanvasElement,
+        seed: &str,
+        config_json: &str,
+    ) -> Result<WorldRenderer, JsValue> {
+        let seed = WorldSeed::parse(seed).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let config = parse_config(config_json)?;
+        Self::build(canvas, seed.value(), config, Theme::dark())
+    }
+    
+    fn build(
+        canvas: HtmlCanvasElement,
+        world_seed: u64,
+        config: WorldConfig,
+        theme: Theme,
+    ) -> Result<WorldRenderer, JsValue> {
         let ctx = canvas
             .get_context("2d")?
             .ok_or("Failed to get 2d context")?
@@ -119,18 +298,33 @@ impl WorldRenderer {
         
         let width = canvas.client_width() as f64;
         let height = canvas.client_height() as f64;
+        let pixel_ratio = get_pixel_ratio(DEFAULT_MAX_PIXEL_RATIO);
         
-        canvas.set_width(width as u32);
-        canvas.set_height(height as u32);
+        canvas.set_width((width * pixel_ratio).round() as u32);
+        canvas.set_height((height * pixel_ratio).round() as u32);
         
+        let chunks = ChunkCache::with_config(world_seed, config)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
         Ok(WorldRenderer {
             canvas,
             ctx,
             camera: Camera::new(width, height),
-            chunks: ChunkCache::new(world_seed),
+            cache_capacity: chunks.config().cache_capacity,
+            chunks,
+            theme,
+            layers: LayerStack::new(),
+            annotations: AnnotationStore::new(),
+            terrain_tiles: HashMap::new(),
+            chunk_roads: HashMap::new(),
+            chunk_borders: HashMap::new(),
+            world_seed,
+            regions: HashMap::new(),
             salesman_paths: Vec::new(),
             animation_start_time: get_time_seconds(),
+            pixel_ratio,
+            max_pixel_ratio: DEFAULT_MAX_PIXEL_RATIO,
             running: false,
+            recent_evictions: Vec::new(),
             last_visible_chunks: 0,
             last_total_cities: 0,
         })
@@ -179,6 +373,238 @@ impl WorldRenderer {
         ).into());
     }
 
+    /// Replace the active theme
+    #[wasm_bindgen]
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.terrain_tiles.clear();
+    }
+    
+    /// Switch to a built-in theme ("dark", "light", "high_contrast")
+    #[wasm_bindgen]
+    pub fn set_theme_preset(&mut self, name: &str) -> Result<(), JsValue> {
+        let theme = Theme::preset(name)
+            .ok_or_else(|| JsValue::from_str(&format!("Unknown theme preset: {}", name)))?;
+        self.set_theme(theme);
+        Ok(())
+    }
+    
+    /// Get a copy of the active theme
+    #[wasm_bindgen]
+    pub fn theme(&self) -> Theme {
+        self.theme.clone()
+    }
+
+    /// Show or hide a layer by name
+    /// ("background", "grid", "cities", "trails", "salesmen", "overlays", "debug")
+    #[wasm_bindgen]
+    pub fn set_layer_enabled(&mut self, name: &str, enabled: bool) -> Result<(), JsValue> {
+        let layer = parse_layer(name)?;
+        self.layers.set_enabled(layer, enabled);
+        Ok(())
+    }
+    
+    /// Check whether a layer is enabled
+    #[wasm_bindgen]
+    pub fn is_layer_enabled(&self, name: &str) -> Result<bool, JsValue> {
+        Ok(self.layers.state(parse_layer(name)?).enabled)
+    }
+    
+    /// Set layer opacity (0.0-1.0)
+    #[wasm_bindgen]
+    pub fn set_layer_opacity(&mut self, name: &str, opacity: f64) -> Result<(), JsValue> {
+        let layer = parse_layer(name)?;
+        self.layers.set_opacity(layer, opacity);
+        Ok(())
+    }
+    
+    /// Set layer draw order, bottom first. Unlisted layers are drawn after the
+    /// listed ones, keeping their current relative order.
+    #[wasm_bindgen]
+    pub fn set_layer_order(&mut self, names: Vec<String>) -> Result<(), JsValue> {
+        let order = names
+            .iter()
+            .map(|n| parse_layer(n))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.layers.set_order(&order);
+        Ok(())
+    }
+    
+    /// Current layer names in draw order, bottom first
+    #[wasm_bindgen]
+    pub fn layer_order(&self) -> Vec<String> {
+        self.layers.order().map(|l| l.name().to_string()).collect()
+    }
+    
+    /// Reset all layers to default order, visibility and opacity
+    #[wasm_bindgen]
+    pub fn reset_layers(&mut self) {
+        self.layers = LayerStack::new();
+    }
+    
+    /// Add a marker at a world position and return its id
+    #[wasm_bindgen]
+    pub fn add_marker(
+        &mut self,
+        x: f64,
+        y: f64,
+        icon: String,
+        color: u32,
+        label: String,
+    ) -> Result<u32, JsValue> {
+        self.annotations
+            .add(color, label, Shape::Marker { x, y, icon })
+            .map_err(|e| JsValue::from_str(&e))
+    }
+    
+    /// Update an existing marker. Returns false if `id` is not a marker or the
+    /// position is not finite.
+    #[wasm_bindgen]
+    pub fn update_marker(
+        &mut self,
+        id: u32,
+        x: f64,
+        y: f64,
+        icon: String,
+        color: u32,
+        label: String,
+    ) -> bool {
+        let shape = Shape::Marker { x, y, icon };
+        if !shape.is_finite() {
+            return false;
+        }
+        match self.annotations.get_mut(id) {
+            Some(a) if matches!(a.shape, Shape::Marker { .. }) => {
+                a.shape = shape;
+                a.color = color;
+                a.label = label;
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    /// Add a polyline. Points format: [x1, y1, x2, y2, ...] in world coordinates
+    #[wasm_bindgen]
+    pub fn add_polyline(
+        &mut self,
+        points: Vec<f64>,
+        color: u32,
+        width: f64,
+        label: String,
+    ) -> Result<u32, JsValue> {
+        let points = points_from_flat(&points).map_err(|e| JsValue::from_str(&e))?;
+        self.annotations
+            .add(color, label, Shape::Polyline { points, width })
+            .map_err(|e| JsValue::from_str(&e))
+    }
+    
+    /// Add a filled area. Points format: [x1, y1, x2, y2, ...] in world coordinates
+    #[wasm_bindgen]
+    pub fn add_area(&mut self, points: Vec<f64>, color: u32, label: String) -> Result<u32, JsValue> {
+        let points = points_from_flat(&points).map_err(|e| JsValue::from_str(&e))?;
+        self.annotations
+            .add(color, label, Shape::Area { points })
+            .map_err(|e| JsValue::from_str(&e))
+    }
+    
+    /// Replace the points of a polyline or area. Returns false if `id` is not
+    /// one, a coordinate is missing or a point is not finite.
+    #[wasm_bindgen]
+    pub fn update_annotation_points(&mut self, id: u32, points: Vec<f64>) -> bool {
+        let new_points = match points_from_flat(&points) {
+            Ok(points) if points_finite(&points) => points,
+            _ => return false,
+        };
+        match self.annotations.get_mut(id).map(|a| &mut a.shape) {
+            Some(Shape::Polyline { points, .. }) | Some(Shape::Area { points }) => {
+                *points = new_points;
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    /// Change colour and label of any annotation
+    #[wasm_bindgen]
+    pub fn update_annotation_style(&mut self, id: u32, color: u32, label: String) -> bool {
+        match self.annotations.get_mut(id) {
+            Some(a) => {
+                a.color = color;
+                a.label = label;
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Remove an annotation. Returns false if it did not exist.
+    #[wasm_bindgen]
+    pub fn remove_annotation(&mut self, id: u32) -> bool {
+        self.annotations.remove(id)
+    }
+    
+    #[wasm_bindgen]
+    pub fn clear_annotations(&mut self) {
+        self.annotations.clear();
+    }
+    
+    /// Topmost annotation at a screen position (CSS pixels)
+    #[wasm_bindgen]
+    pub fn pick_annotation(&self, screen_x: f64, screen_y: f64) -> Option<u32> {
+        let (wx, wy) = self.camera.screen_to_world(screen_x, screen_y);
+        self.annotations
+            .hit_test(wx, wy, 1.0 / self.camera.zoom, self.theme.marker_radius)
+    }
+    
+    /// Export all annotations as JSON
+    #[wasm_bindgen]
+    pub fn export_annotations(&self) -> String {
+        self.annotations.to_json()
+    }
+    
+    /// Replace all annotations with ones from `export_annotations` JSON
+    #[wasm_bindgen]
+    pub fn import_annotations(&mut self, json: &str) -> Result<(), JsValue> {
+        self.annotations.load_json(json).map_err(|e| JsValue::from_str(&e))
+    }
+    
+    /// Select city placement: "uniform" or "blue_noise" (with `min_distance` in cells).
+    /// Version 1 worlds only place uniformly; blue noise needs a config with
+    /// `generator_version` 2 or later.
+    #[wasm_bindgen]
+    pub fn set_placement(&mut self, name: &str, min_distance: f64) -> Result<(), JsValue> {
+        let strategy = PlacementStrategy::from_name(name, min_distance)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.chunks
+            .set_strategy(strategy)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.chunk_roads.clear();
+        self.chunk_borders.clear();
+        // Hub spacing follows the placement separation
+        self.regions.clear();
+        Ok(())
+    }
+    
+    /// Current world configuration as JSON
+    #[wasm_bindgen]
+    pub fn config(&self) -> String {
+        self.chunks.config().to_json()
+    }
+    
+    /// Cap the device pixel ratio used for the backing store.
+    /// Lower values trade sharpness for fill rate on high-density phones.
+    #[wasm_bindgen]
+    pub fn set_max_pixel_ratio(&mut self, max: f64) {
+        self.max_pixel_ratio = if max.is_finite() { max.max(1.0) } else { DEFAULT_MAX_PIXEL_RATIO };
+    }
+    
+    /// Pixel ratio applied in the last frame (backing store pixels per CSS pixel)
+    #[wasm_bindgen]
+    pub fn pixel_ratio(&self) -> f64 {
+        self.pixel_ratio
+    }
+
     /// Pan camera by screen delta
     #[wasm_bindgen]
     pub fn pan(&mut self, dx: f64, dy: f64) {
@@ -202,33 +628,34 @@ impl WorldRenderer {
     /// Render a single frame and return stats
     #[wasm_bindgen]
     pub fn render(&mut self) -> RenderStats {
-        // Update canvas size if needed
+        // Update canvas size if needed (camera works in CSS pixels)
         let width = self.canvas.client_width() as f64;
         let height = self.canvas.client_height() as f64;
+        let pixel_ratio = get_pixel_ratio(self.max_pixel_ratio);
         
-        if width != self.camera.width || height != self.camera.height {
-            self.canvas.set_width(width as u32);
-            self.canvas.set_height(height as u32);
+        if width != self.camera.width
+            || height != self.camera.height
+            || pixel_ratio != self.pixel_ratio
+        {
+            self.canvas.set_width((width * pixel_ratio).round() as u32);
+            self.canvas.set_height((height * pixel_ratio).round() as u32);
             self.camera.resize(width, height);
+            self.pixel_ratio = pixel_ratio;
         }
         
-        // Clear background
-        self.ctx.set_fill_style_str("#0D0D0D");
-        self.ctx.fill_rect(0.0, 0.0, width, height);
-        
-        // Draw grid lines
-        self.draw_grid();
+        // Map CSS pixels onto the backing store
+        self.ctx
+            .set_transform(pixel_ratio, 0.0, 0.0, pixel_ratio, 0.0, 0.0)
+            .ok();
         
-        // Draw salesman paths (trails)
-        self.draw_salesman_trails();
-        
-        // Draw salesmen (animated positions)
-        self.draw_salesmen();
-        
-        // Update stats and chunks
+        // Update stats and chunks (before drawing so layers can read them)
         let visible = self.get_visible_coords();
         self.last_visible_chunks = visible.len() as u32;
         
+        // Hold every chunk this frame touches, so none is evicted before it is drawn
+        let capacity = self.cache_capacity.max(frame_chunk_count(&visible));
+        self.chunks.set_capacity(capacity);
+        
         let mut total_cities = 0u32;
         for coord in &visible {
             let chunk = self.chunks.get_or_generate(*coord);
@@ -236,6 +663,38 @@ impl WorldRenderer {
         }
         
         self.last_total_cities = total_cities;
+        self.track_evictions();
+        if self.layers.is_visible(Layer::Terrain) {
+            self.prepare_terrain_tiles(&visible);
+        }
+        if self.layers.is_visible(Layer::Roads) {
+            self.prepare_roads(&visible);
+        }
+        if self.layers.is_visible(Layer::Borders) {
+            self.prepare_borders(&visible);
+        }
+        self.prepare_regions(&visible);
+        
+        // Draw layers bottom to top
+        self.ctx.clear_rect(0.0, 0.0, width, height);
+        for state in self.layers.visible() {
+            self.ctx.set_global_alpha(state.opacity);
+            match state.layer {
+                Layer::Background => self.draw_background(),
+                Layer::Terrain => self.draw_terrain(&visible),
+                Layer::Rivers => self.draw_rivers(&visible),
+                Layer::Grid => self.draw_grid(),
+                Layer::Roads => self.draw_roads(&visible),
+                Layer::Borders => self.draw_borders(&visible),
+                Layer::Cities => self.draw_cities(&visible),
+                Layer::Trails => self.draw_salesman_trails(),
+                Layer::Salesmen => self.draw_salesmen(),
+                Layer::Overlays => self.draw_annotations(),
+                Layer::Debug => self.draw_debug_chunks(&visible),
+            }
+        }
+        self.ctx.set_global_alpha(1.0);
+        
         self.chunks.advance_frame();
 
         RenderStats {
@@ -249,8 +708,350 @@ impl WorldRenderer {
         }
     }
 
+    fn draw_background(&self) {
+        self.ctx.set_fill_style_str(&self.theme.background);
+        self.ctx.fill_rect(0.0, 0.0, self.camera.width, self.camera.height);
+    }
+    
+    /// Build terrain tiles for visible chunks and drop tiles of evicted chunks
+    fn prepare_terrain_tiles(&mut self, visible: &[ChunkCoord]) {
+        let chunks = &self.chunks;
+        self.terrain_tiles.retain(|coord, _| chunks.get(*coord).is_some());
+        
+        for coord in visible {
+            if self.terrain_tiles.contains_key(coord) {
+                continue;
+            }
+            let Some(chunk) = self.chunks.get(*coord) else {
+                continue;
+            };
+            // Uniform chunks (version 1, layered chunks past the i32 cells) get no tile
+            if chunk.terrain.uniform_biome().is_some() {
+                continue;
+            }
+            if let Some(tile) = build_terrain_tile(&chunk.terrain, &self.theme) {
+                self.terrain_tiles.insert(*coord, tile);
+            }
+        }
+    }
+    
+    fn draw_terrain(&self, visible: &[ChunkCoord]) {
+        let ctx = &self.ctx;
+        // Keep cells crisp when scaled up
+        ctx.set_image_smoothing_enabled(false);
+        for coord in visible {
+            let Some(tile) = self.terrain_tiles.get(coord) else {
+                continue;
+            };
+            let (sx, sy, size) = self.chunk_screen_rect(*coord);
+            ctx.draw_image_with_html_canvas_element_and_dw_and_dh(tile, sx, sy, size, size).ok();
+        }
+        ctx.set_image_smoothing_enabled(true);
+    }
+    
+    /// Rivers through visible chunks, each drawn once through cell centres
+    fn draw_rivers(&self, visible: &[ChunkCoord]) {
+        let ctx = &self.ctx;
+        ctx.set_stroke_style_str(&self.theme.river);
+        ctx.set_line_width(self.theme.river_width);
+        ctx.set_line_join("round");
+        ctx.begin_path();
+        
+        let mut drawn = HashSet::new();
+        for coord in visible {
+            let Some(chunk) = self.chunks.get(*coord) else {
+                continue;
+            };
+            for river in &chunk.rivers {
+                if !drawn.insert(river.source_chunk) {
+                    continue;
+                }
+                for (i, (x, y)) in river.points.iter().enumerate() {
+                    let (sx, sy) = self.camera.world_to_screen(*x as f64 + 0.5, *y as f64 + 0.5);
+                    if i == 0 {
+                        ctx.move_to(sx, sy);
+                    } else {
+                        ctx.line_to(sx, sy);
+                    }
+                }
+            }
+        }
+        
+        ctx.stroke();
+        ctx.set_line_join("miter");
+    }
+    
+    /// Derive roads for visible chunks, a few per frame to avoid hitches
+    fn prepare_roads(&mut self, visible: &[ChunkCoord]) {
+        let chunks = &self.chunks;
+        self.chunk_roads.retain(|coord, _| chunks.get(*coord).is_some());
+        
+        let mut budget = ROAD_BUILDS_PER_FRAME;
+        for coord in visible {
+            if budget == 0 {
+                break;
+            }
+            if !self.chunk_roads.contains_key(coord) {
+                let roads = roads_touching_chunk(&mut self.chunks, *coord);
+                self.chunk_roads.insert(*coord, roads);
+                budget -= 1;
+            }
+        }
+    }
+    
+    fn draw_roads(&self, visible: &[ChunkCoord]) {
+        let ctx = &self.ctx;
+        ctx.set_stroke_style_str(&self.theme.road);
+        ctx.set_line_width(self.theme.road_width);
+        ctx.begin_path();
+        
+        for coord in visible {
+            let Some(roads) = self.chunk_roads.get(coord) else {
+                continue;
+            };
+            for road in roads {
+                let (ax, ay) = self.camera.world_to_screen(road.from.0 as f64, road.from.1 as f64);
+                let (bx, by) = self.camera.world_to_screen(road.to.0 as f64, road.to.1 as f64);
+                ctx.move_to(ax, ay);
+                ctx.line_to(bx, by);
+            }
+        }
+        
+        ctx.stroke();
+    }
+    
+    /// Derive territory borders for visible chunks, a few per frame
+    fn prepare_borders(&mut self, visible: &[ChunkCoord]) {
+        let chunks = &self.chunks;
+        self.chunk_borders.retain(|coord, _| chunks.get(*coord).is_some());
+        
+        let mut budget = BORDER_BUILDS_PER_FRAME;
+        for coord in visible {
+            if budget == 0 {
+                break;
+            }
+            if !self.chunk_borders.contains_key(coord) {
+                let territories =
+                    territories_for_chunk(&mut self.chunks, *coord, self.world_seed);
+                self.chunk_borders.insert(*coord, territories.borders);
+                budget -= 1;
+            }
+        }
+    }
+    
+    fn draw_borders(&self, visible: &[ChunkCoord]) {
+        let ctx = &self.ctx;
+        let theme = &self.theme;
+        
+        // Territory borders underneath national ones
+        for (national, color, width) in [
+            (false, &theme.territory_border, theme.territory_border_width),
+            (true, &theme.nation_border, theme.nation_border_width),
+        ] {
+            ctx.set_stroke_style_str(color);
+            ctx.set_line_width(width);
+            ctx.begin_path();
+            for coord in visible {
+                let Some(borders) = self.chunk_borders.get(coord) else {
+                    continue;
+                };
+                for border in borders.iter().filter(|b| b.national == national) {
+                    let (ax, ay) = self.camera.world_to_screen(border.from.0 as f64, border.from.1 as f64);
+                    let (bx, by) = self.camera.world_to_screen(border.to.0 as f64, border.to.1 as f64);
+                    ctx.move_to(ax, ay);
+                    ctx.line_to(bx, by);
+                }
+            }
+            ctx.stroke();
+        }
+    }
+    
+    /// Keep region data for exactly the regions in view
+    fn prepare_regions(&mut self, visible: &[ChunkCoord]) {
+        let in_view: HashSet<RegionCoord> =
+            visible.iter().map(|c| RegionCoord::from_chunk(*c)).collect();
+        self.regions.retain(|coord, _| in_view.contains(coord));
+        for coord in in_view {
+            if !self.regions.contains_key(&coord) {
+                self.regions.insert(coord, self.chunks.region_data(coord));
+            }
+        }
+    }
+    
+    fn draw_cities(&self, visible: &[ChunkCoord]) {
+        let ctx = &self.ctx;
+        let radius = self.theme.city_radius.min(self.camera.zoom * 0.4);
+        
+        ctx.set_fill_style_str(&self.theme.city);
+        ctx.begin_path();
+        
+        for coord in visible {
+            let Some(chunk) = self.chunks.get(*coord) else {
+                continue;
+            };
+            for city in &chunk.cities {
+                let (x, y) = city.world_position(coord, chunk.size);
+                let (sx, sy) = self.camera.world_to_screen(x, y);
+                ctx.move_to(sx + radius, sy);
+                ctx.arc(sx, sy, radius, 0.0, std::f64::consts::TAU).ok();
+            }
+        }
+        
+        ctx.fill();
+        
+        // Rings around capitals and trade hubs
+        ctx.set_line_width(2.0);
+        for region in self.regions.values() {
+            for (index, (x, y)) in region.hubs().enumerate() {
+                let is_capital = index == 0 && region.capital.is_some();
+                let (sx, sy) = self.camera.world_to_screen(x as f64, y as f64);
+                let ring = radius * if is_capital { 3.0 } else { 2.0 };
+                ctx.set_stroke_style_str(if is_capital { &self.theme.capital } else { &self.theme.trade_hub });
+                ctx.begin_path();
+                ctx.arc(sx, sy, ring, 0.0, std::f64::consts::TAU).ok();
+                ctx.stroke();
+            }
+        }
+    }
+
+    fn draw_annotations(&self) {
+        let ctx = &self.ctx;
+        let theme = &self.theme;
+        let base_alpha = ctx.global_alpha();
+        
+        ctx.set_font(&theme.font);
+        ctx.set_text_baseline("middle");
+        
+        for annotation in self.annotations.iter() {
+            let color = format!("#{:06x}", annotation.color & 0xFFFFFF);
+            let label_at = match &annotation.shape {
+                Shape::Marker { x, y, icon } => {
+                    let (sx, sy) = self.camera.world_to_screen(*x, *y);
+                    ctx.set_fill_style_str(&color);
+                    ctx.begin_path();
+                    ctx.arc(sx, sy, theme.marker_radius, 0.0, std::f64::consts::TAU).ok();
+                    ctx.fill();
+                    if !icon.is_empty() {
+                        ctx.set_text_align("center");
+                        ctx.set_fill_style_str(&theme.label);
+                        ctx.fill_text(icon, sx, sy).ok();
+                        ctx.set_text_align("start");
+                    }
+                    Some((sx + theme.marker_radius + 4.0, sy))
+                }
+                Shape::Polyline { points, width } => {
+                    self.trace_path(points, false);
+                    ctx.set_stroke_style_str(&color);
+                    ctx.set_line_width(*width);
+                    ctx.stroke();
+                    points.first().map(|p| self.camera.world_to_screen(p.0, p.1))
+                }
+                Shape::Area { points } => {
+                    self.trace_path(points, true);
+                    ctx.set_fill_style_str(&color);
+                    ctx.set_global_alpha(base_alpha * theme.area_alpha);
+                    ctx.fill();
+                    ctx.set_global_alpha(base_alpha);
+                    ctx.set_stroke_style_str(&color);
+                    ctx.set_line_width(1.0);
+                    ctx.stroke();
+                    points.first().map(|p| self.camera.world_to_screen(p.0, p.1))
+                }
+            };
+            
+            if let Some((lx, ly)) = label_at.filter(|_| !annotation.label.is_empty()) {
+                ctx.set_fill_style_str(&theme.label);
+                ctx.fill_text(&annotation.label, lx, ly).ok();
+            }
+        }
+        
+        ctx.set_text_baseline("alphabetic");
+    }
+    
+    /// Begin a screen-space path through world-space points
+    fn trace_path(&self, points: &[(f64, f64)], closed: bool) {
+        let ctx = &self.ctx;
+        ctx.begin_path();
+        for (i, (x, y)) in points.iter().enumerate() {
+            let (sx, sy) = self.camera.world_to_screen(*x, *y);
+            if i == 0 {
+                ctx.move_to(sx, sy);
+            } else {
+                ctx.line_to(sx, sy);
+            }
+        }
+        if closed {
+            ctx.close_path();
+        }
+    }
+    
+    fn track_evictions(&mut self) {
+        let frame = self.chunks.frame();
+        self.recent_evictions
+            .retain(|(_, at)| frame - at < EVICTION_HIGHLIGHT_FRAMES);
+        for coord in self.chunks.evicted_this_frame() {
+            self.recent_evictions.push((*coord, frame));
+        }
+    }
+    
+    /// Screen-space rectangle (x, y, size) covered by a chunk
+    fn chunk_screen_rect(&self, coord: ChunkCoord) -> (f64, f64, f64) {
+        let size = self.chunks.chunk_size();
+        let (origin_x, origin_y) = coord.world_origin(size);
+        let (sx, sy) = self.camera.world_to_screen(origin_x as f64, origin_y as f64);
+        (sx, sy, size as f64 * self.camera.zoom)
+    }
+    
+    /// Outline visible chunks: orange = generated this frame, green = served from cache,
+    /// red = evicted recently (fades out)
+    fn draw_debug_chunks(&self, visible: &[ChunkCoord]) {
+        let ctx = &self.ctx;
+        let frame = self.chunks.frame();
+        let base_alpha = ctx.global_alpha();
+        
+        ctx.set_line_width(1.0);
+        ctx.set_font(&self.theme.font);
+        
+        for coord in visible {
+            let Some(chunk) = self.chunks.get(*coord) else {
+                continue;
+            };
+            let (sx, sy, size) = self.chunk_screen_rect(*coord);
+            let fresh = chunk.generated_at == frame;
+            
+            ctx.set_stroke_style_str(if fresh { DEBUG_FRESH_COLOR } else { DEBUG_CACHED_COLOR });
+            ctx.stroke_rect(sx, sy, size, size);
+            
+            // Only label chunks big enough to fit the text
+            if size < 80.0 {
+                continue;
+            }
+            ctx.set_fill_style_str(&self.theme.label);
+            let lines = [
+                format!("({}, {})", coord.x, coord.y),
+                format!("cities {}", chunk.cities.len()),
+                format!("used {}", chunk.last_used),
+                if fresh { "fresh".to_string() } else { "cached".to_string() },
+            ];
+            for (i, line) in lines.iter().enumerate() {
+                ctx.fill_text(line, sx + 4.0, sy + 12.0 * (i as f64 + 1.0)).ok();
+            }
+        }
+        
+        ctx.set_stroke_style_str(DEBUG_EVICTED_COLOR);
+        ctx.set_line_width(2.0);
+        for (coord, at) in &self.recent_evictions {
+            let (sx, sy, size) = self.chunk_screen_rect(*coord);
+            let fade = 1.0 - (frame - at) as f64 / EVICTION_HIGHLIGHT_FRAMES as f64;
+            ctx.set_global_alpha(base_alpha * fade);
+            ctx.stroke_rect(sx, sy, size, size);
+        }
+        ctx.set_global_alpha(base_alpha);
+    }
+
     fn draw_salesman_trails(&self) {
         let ctx = &self.ctx;
+        let theme = &self.theme;
         
         for path in &self.salesman_paths {
             if path.waypoints.len() < 2 {
@@ -258,12 +1059,10 @@ impl WorldRenderer {
             }
             
             // Draw path trail
-            let r = (path.color >> 16) & 0xFF;
-            let g = (path.color >> 8) & 0xFF;
-            let b = path.color & 0xFF;
+            let (r, g, b) = rgb(path.color);
             
-            ctx.set_stroke_style_str(&format!("rgba({}, {}, {}, 0.3)", r, g, b));
-            ctx.set_line_width(2.0);
+            ctx.set_stroke_style_str(&format!("rgba({}, {}, {}, {})", r, g, b, theme.trail_alpha));
+            ctx.set_line_width(theme.trail_line_width);
             ctx.begin_path();
             
             let (sx, sy) = self.camera.world_to_screen(
@@ -280,11 +1079,11 @@ impl WorldRenderer {
             ctx.stroke();
             
             // Draw waypoint dots
-            ctx.set_fill_style_str(&format!("rgba({}, {}, {}, 0.5)", r, g, b));
+            ctx.set_fill_style_str(&format!("rgba({}, {}, {}, {})", r, g, b, theme.waypoint_alpha));
             for wp in &path.waypoints {
                 let (wx, wy) = self.camera.world_to_screen(wp.x, wp.y);
                 ctx.begin_path();
-                ctx.arc(wx, wy, 3.0, 0.0, std::f64::consts::TAU).ok();
+                ctx.arc(wx, wy, theme.waypoint_radius, 0.0, std::f64::consts::TAU).ok();
                 ctx.fill();
             }
         }
@@ -292,6 +1091,7 @@ impl WorldRenderer {
 
     fn draw_salesmen(&self) {
         let ctx = &self.ctx;
+        let theme = &self.theme;
         let elapsed = get_time_seconds() - self.animation_start_time;
         
         for path in &self.salesman_paths {
@@ -299,30 +1099,29 @@ impl WorldRenderer {
             let (screen_x, screen_y) = self.camera.world_to_screen(world_x, world_y);
             
             // Extract RGB
-            let r = (path.color >> 16) & 0xFF;
-            let g = (path.color >> 8) & 0xFF;
-            let b = path.color & 0xFF;
+            let (r, g, b) = rgb(path.color);
             
             // Draw glow
             ctx.set_shadow_color(&format!("rgb({}, {}, {})", r, g, b));
-            ctx.set_shadow_blur(15.0);
+            ctx.set_shadow_blur(theme.glow_strength);
             
             // Draw body
             ctx.set_fill_style_str(&format!("#{:06x}", path.color));
             ctx.begin_path();
-            ctx.arc(screen_x, screen_y, 8.0, 0.0, std::f64::consts::TAU).ok();
+            ctx.arc(screen_x, screen_y, theme.salesman_radius, 0.0, std::f64::consts::TAU).ok();
             ctx.fill();
             
             // Draw border
             ctx.set_shadow_blur(0.0);
-            ctx.set_stroke_style_str("white");
-            ctx.set_line_width(2.0);
+            ctx.set_stroke_style_str(&theme.salesman_border);
+            ctx.set_line_width(theme.salesman_border_width);
             ctx.stroke();
             
             // Draw ID label
-            ctx.set_fill_style_str("white");
-            ctx.set_font("10px monospace");
-            ctx.fill_text(&format!("{}", path.id), screen_x + 12.0, screen_y + 4.0).ok();
+            ctx.set_fill_style_str(&theme.label);
+            ctx.set_font(&theme.font);
+            let label_offset = theme.salesman_radius + 4.0;
+            ctx.fill_text(&format!("{}", path.id), screen_x + label_offset, screen_y + 4.0).ok();
         }
     }
 
@@ -343,8 +1142,8 @@ impl WorldRenderer {
         let width = self.camera.width;
         let height = self.camera.height;
         
-        ctx.set_stroke_style_str("#2A2A2A");
-        ctx.set_line_width(1.0);
+        ctx.set_stroke_style_str(&self.theme.grid);
+        ctx.set_line_width(self.theme.grid_line_width);
         
         // Calculate visible grid range
         let start_x = self.camera.x.floor() as i32;
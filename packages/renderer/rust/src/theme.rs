@@ -0,0 +1,233 @@
+//! Visual theme for the renderer
+//!
+//! Colours are CSS colour strings so they can be handed straight to Canvas2D.
+
+use opengrid_world::Biome;
+use wasm_bindgen::prelude::*;
+
+/// Palette, line widths, radii and fonts used by `WorldRenderer`
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// Canvas clear colour
+    pub background: String,
+    /// Grid line colour
+    pub grid: String,
+    pub grid_line_width: f64,
+
+    /// Biome colours, packed 0xRRGGBB (written straight into pixel buffers)
+    pub ocean: u32,
+    pub plains: u32,
+    pub forest: u32,
+    pub mountains: u32,
+    pub desert: u32,
+    pub lake: u32,
+
+    /// River colour
+    pub river: String,
+    pub river_width: f64,
+
+    /// Road colour
+    pub road: String,
+    pub road_width: f64,
+
+    /// Border between city territories
+    pub territory_border: String,
+    pub territory_border_width: f64,
+    /// Border between nations
+    pub nation_border: String,
+    pub nation_border_width: f64,
+
+    /// City dot colour
+    pub city: String,
+    /// City dot radius in CSS pixels (shrinks with zoom so dots never overlap)
+    pub city_radius: f64,
+    /// Ring around region capitals
+    pub capital: String,
+    /// Ring around trade hubs
+    pub trade_hub: String,
+
+    /// Annotation marker radius in CSS pixels
+    pub marker_radius: f64,
+    /// Fill opacity of annotation areas (0.0-1.0)
+    pub area_alpha: f64,
+
+    /// Opacity of salesman path trails (0.0-1.0)
+    pub trail_alpha: f64,
+    pub trail_line_width: f64,
+    /// Opacity of waypoint dots (0.0-1.0)
+    pub waypoint_alpha: f64,
+    pub waypoint_radius: f64,
+
+    pub salesman_radius: f64,
+    pub salesman_border: String,
+    pub salesman_border_width: f64,
+    /// Shadow blur around salesmen, 0.0 disables the glow
+    pub glow_strength: f64,
+
+    /// Label text colour
+    pub label: String,
+    /// CSS font shorthand for labels
+    pub font: String,
+}
+
+#[wasm_bindgen]
+impl Theme {
+    /// Default theme (dark)
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Theme {
+        Self::dark()
+    }
+
+    /// Dark theme - the original OpenGrid look
+    pub fn dark() -> Theme {
+        Theme {
+            background: "#0D0D0D".to_string(),
+            grid: "#2A2A2A".to_string(),
+            grid_line_width: 1.0,
+            ocean: 0x0B1A2A,
+            plains: 0x1A2218,
+            forest: 0x122016,
+            mountains: 0x2A2826,
+            desert: 0x2A2418,
+            lake: 0x10243A,
+            river: "#1F4E79".to_string(),
+            river_width: 2.0,
+            road: "#3A3A3A".to_string(),
+            road_width: 1.5,
+            territory_border: "rgba(255, 255, 255, 0.08)".to_string(),
+            territory_border_width: 1.0,
+            nation_border: "#8E6BBF".to_string(),
+            nation_border_width: 2.0,
+            city: "#4FC3F7".to_string(),
+            city_radius: 3.0,
+            capital: "#FFD54F".to_string(),
+            trade_hub: "#FF8A65".to_string(),
+            marker_radius: 7.0,
+            area_alpha: 0.2,
+            trail_alpha: 0.3,
+            trail_line_width: 2.0,
+            waypoint_alpha: 0.5,
+            waypoint_radius: 3.0,
+            salesman_radius: 8.0,
+            salesman_border: "white".to_string(),
+            salesman_border_width: 2.0,
+            glow_strength: 15.0,
+            label: "white".to_string(),
+            font: "10px monospace".to_string(),
+        }
+    }
+
+    /// Light theme for daytime use
+    pub fn light() -> Theme {
+        Theme {
+            background: "#F5F5F2".to_string(),
+            grid: "#D6D6D0".to_string(),
+            grid_line_width: 1.0,
+            ocean: 0xA8CBE0,
+            plains: 0xE4EAD2,
+            forest: 0xC5D9B5,
+            mountains: 0xCFC8BE,
+            desert: 0xEFE2C0,
+            lake: 0x8FBCD8,
+            river: "#5B9BD5".to_string(),
+            river_width: 2.0,
+            road: "#B8B2A6".to_string(),
+            road_width: 1.5,
+            territory_border: "rgba(0, 0, 0, 0.1)".to_string(),
+            territory_border_width: 1.0,
+            nation_border: "#7A4FA8".to_string(),
+            nation_border_width: 2.0,
+            city: "#0277BD".to_string(),
+            city_radius: 3.0,
+            capital: "#C79100".to_string(),
+            trade_hub: "#D84315".to_string(),
+            marker_radius: 7.0,
+            area_alpha: 0.2,
+            trail_alpha: 0.45,
+            trail_line_width: 2.0,
+            waypoint_alpha: 0.7,
+            waypoint_radius: 3.0,
+            salesman_radius: 8.0,
+            salesman_border: "#1A1A1A".to_string(),
+            salesman_border_width: 2.0,
+            glow_strength: 6.0,
+            label: "#1A1A1A".to_string(),
+            font: "10px monospace".to_string(),
+        }
+    }
+
+    /// High-contrast theme: pure black/white, thicker strokes, no glow
+    pub fn high_contrast() -> Theme {
+        Theme {
+            background: "#000000".to_string(),
+            grid: "#5C5C5C".to_string(),
+            grid_line_width: 1.0,
+            ocean: 0x000040,
+            plains: 0x103010,
+            forest: 0x004000,
+            mountains: 0x404040,
+            desert: 0x403000,
+            lake: 0x000060,
+            river: "#3399FF".to_string(),
+            river_width: 3.0,
+            road: "#FFFFFF".to_string(),
+            road_width: 2.0,
+            territory_border: "#808080".to_string(),
+            territory_border_width: 1.0,
+            nation_border: "#FF00FF".to_string(),
+            nation_border_width: 3.0,
+            city: "#00FFFF".to_string(),
+            city_radius: 4.0,
+            capital: "#FFFF00".to_string(),
+            trade_hub: "#FF8000".to_string(),
+            marker_radius: 9.0,
+            area_alpha: 0.4,
+            trail_alpha: 0.8,
+            trail_line_width: 3.0,
+            waypoint_alpha: 1.0,
+            waypoint_radius: 4.0,
+            salesman_radius: 10.0,
+            salesman_border: "#FFFFFF".to_string(),
+            salesman_border_width: 3.0,
+            glow_strength: 0.0,
+            label: "#FFFF00".to_string(),
+            font: "bold 12px monospace".to_string(),
+        }
+    }
+
+    /// Look up a built-in preset by name ("dark", "light", "high_contrast")
+    pub fn preset(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high_contrast" | "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    /// Packed colour for a biome
+    pub fn biome_color(&self, biome: Biome) -> u32 {
+        match biome {
+            Biome::Ocean => self.ocean,
+            Biome::Plains => self.plains,
+            Biome::Forest => self.forest,
+            Biome::Mountains => self.mountains,
+            Biome::Desert => self.desert,
+            Biome::Lake => self.lake,
+        }
+    }
+}
+
+/// Split a packed 0xRRGGBB colour into components
+pub(crate) fn rgb(color: u32) -> (u32, u32, u32) {
+    ((color >> 16) & 0xFF, (color >> 8) & 0xFF, color & 0xFF)
+}
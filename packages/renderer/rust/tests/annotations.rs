@@ -0,0 +1,125 @@
+//! Annotation picking and JSON round trips.
+
+use opengrid_renderer::{AnnotationStore, Shape};
+
+fn store() -> AnnotationStore {
+    let mut store = AnnotationStore::new();
+    let square = vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+    store.add(0xFF0000, "zone".into(), Shape::Area { points: square }).unwrap();
+    let line = vec![(0.0, 20.0), (10.0, 20.0)];
+    store
+        .add(0x00FF00, "route".into(), Shape::Polyline { points: line, width: 4.0 })
+        .unwrap();
+    let pin = Shape::Marker {
+        x: 5.0,
+        y: 5.0,
+        icon: "*".into(),
+    };
+    store.add(0x0000FF, "pin".into(), pin).unwrap();
+    store
+}
+
+#[test]
+fn hit_test_prefers_the_topmost_annotation() {
+    let store = store();
+    // At 1 world unit per pixel: marker radius 2 plus 4 pixels of slop
+    assert_eq!(store.hit_test(5.0, 10.9, 1.0, 2.0), Some(3));
+    assert_eq!(store.hit_test(9.5, 1.0, 1.0, 2.0), Some(1));
+    assert_eq!(store.hit_test(5.0, 25.9, 1.0, 2.0), Some(2));
+    assert_eq!(store.hit_test(5.0, 26.1, 1.0, 2.0), None);
+    assert_eq!(store.hit_test(-3.9, 5.0, 1.0, 2.0), Some(1));
+    assert_eq!(store.hit_test(-4.1, 5.0, 1.0, 2.0), None);
+
+    // Zoomed in, the pixel-sized slop shrinks in world units
+    assert_eq!(store.hit_test(5.0, 21.0, 0.1, 2.0), None);
+}
+
+#[test]
+fn export_imports_back_unchanged() {
+    let store = store();
+    let json = store.to_json();
+    let mut imported = AnnotationStore::new();
+    imported.load_json(&json).unwrap();
+    assert_eq!(imported.to_json(), json);
+    assert!(imported.iter().eq(store.iter()));
+
+    // Ids continue after the highest imported one
+    let id = imported.add(0, String::new(), Shape::Area { points: Vec::new() });
+    assert_eq!(id, Ok(4));
+}
+
+#[test]
+fn malformed_documents_are_rejected() {
+    let mut store = store();
+    let marker = r#"{"id":1,"color":0,"kind":"marker","x":1.0,"y":2.0,"icon":"*"}"#;
+    let duplicate = format!(r#"{{"version":1,"annotations":[{},{}]}}"#, marker, marker);
+    assert!(store.load_json(&duplicate).is_err());
+    let null = r#"{"version":1,"annotations":[{"id":1,"color":0,"kind":"marker","x":null,"y":2.0,"icon":"*"}]}"#;
+    assert!(store.load_json(null).is_err());
+    assert!(store.load_json(r#"{"version":2,"annotations":[]}"#).is_err());
+
+    // Failed imports leave the store alone
+    assert_eq!(store.iter().count(), 3);
+}
+
+#[test]
+fn non_finite_shapes_are_refused() {
+    let mut store = AnnotationStore::new();
+    let marker = Shape::Marker {
+        x: f64::NAN,
+        y: 0.0,
+        icon: "*".into(),
+    };
+    assert!(store.add(0, String::new(), marker).is_err());
+    let line = Shape::Polyline {
+        points: vec![(0.0, 0.0), (1.0, 1.0)],
+        width: f64::INFINITY,
+    };
+    assert!(store.add(0, String::new(), line).is_err());
+    assert_eq!(store.iter().count(), 0);
+    assert_eq!(store.to_json(), r#"{"version":1,"annotations":[]}"#);
+}
+
+#[test]
+fn line_widths_must_be_positive() {
+    let mut store = AnnotationStore::new();
+    for width in [0.0, -2.0] {
+        let line = Shape::Polyline {
+            points: vec![(0.0, 0.0), (1.0, 1.0)],
+            width,
+        };
+        assert!(store.add(0, String::new(), line).is_err());
+    }
+    let json = r#"{"version":1,"annotations":[{"id":1,"color":0,"kind":"polyline","points":[],"width":0.0}]}"#;
+    assert!(store.load_json(json).is_err());
+    assert_eq!(store.iter().count(), 0);
+}
+
+#[test]
+fn ids_never_wrap_around() {
+    let marker = |id: u32| {
+        format!(
+            r#"{{"version":1,"annotations":[{{"id":{},"color":0,"kind":"marker","x":0.0,"y":0.0,"icon":"*"}}]}}"#,
+            id
+        )
+    };
+    let pin = || Shape::Marker {
+        x: 1.0,
+        y: 1.0,
+        icon: "*".into(),
+    };
+
+    let mut store = AnnotationStore::new();
+    store.load_json(&marker(u32::MAX - 1)).unwrap();
+    assert_eq!(store.add(0, String::new(), pin()), Ok(u32::MAX));
+    assert!(store.add(0, String::new(), pin()).is_err());
+
+    // The highest id imports fine, but leaves no room for new ones
+    store.load_json(&marker(u32::MAX)).unwrap();
+    assert!(store.add(0, String::new(), pin()).is_err());
+    assert_eq!(store.iter().count(), 1);
+
+    // Clearing by import makes room again
+    store.load_json(r#"{"version":1,"annotations":[]}"#).unwrap();
+    assert_eq!(store.add(0, String::new(), pin()), Ok(1));
+}
@@ -0,0 +1,64 @@
+//! Layer ordering and visibility.
+
+use opengrid_renderer::{Layer, LayerStack};
+
+fn names(layers: impl Iterator<Item = Layer>) -> Vec<&'static str> {
+    layers.map(|l| l.name()).collect()
+}
+
+#[test]
+fn default_stack_draws_everything_but_debug() {
+    let stack = LayerStack::new();
+    assert_eq!(stack.order().collect::<Vec<_>>(), Layer::ALL);
+    let visible: Vec<_> = stack.visible().iter().map(|s| s.layer).collect();
+    assert_eq!(visible, &Layer::ALL[..Layer::ALL.len() - 1]);
+    assert!(!stack.is_visible(Layer::Debug));
+}
+
+#[test]
+fn listed_layers_move_to_the_front_in_order() {
+    let mut stack = LayerStack::new();
+    stack.set_order(&[Layer::Salesmen, Layer::Background, Layer::Salesmen]);
+    assert_eq!(
+        names(stack.order()),
+        [
+            "salesmen",
+            "background",
+            "terrain",
+            "rivers",
+            "grid",
+            "roads",
+            "borders",
+            "cities",
+            "trails",
+            "overlays",
+            "debug"
+        ]
+    );
+
+    // Settings travel with their layer
+    stack.set_opacity(Layer::Cities, 0.5);
+    stack.set_order(&[Layer::Cities]);
+    assert_eq!(stack.order().next(), Some(Layer::Cities));
+    assert_eq!(stack.state(Layer::Cities).opacity, 0.5);
+    assert_eq!(stack.order().count(), Layer::ALL.len());
+}
+
+#[test]
+fn hidden_and_transparent_layers_are_not_visible() {
+    let mut stack = LayerStack::new();
+    stack.set_enabled(Layer::Trails, false);
+    stack.set_opacity(Layer::Grid, 0.0);
+    stack.set_enabled(Layer::Debug, true);
+    stack.set_opacity(Layer::Debug, 2.0);
+    stack.set_opacity(Layer::Roads, f64::NAN);
+
+    let visible = stack.visible();
+    let layers: Vec<_> = visible.iter().map(|s| s.layer).collect();
+    assert!(!layers.contains(&Layer::Trails));
+    assert!(!layers.contains(&Layer::Grid));
+    assert_eq!(layers.last(), Some(&Layer::Debug));
+    assert_eq!(stack.state(Layer::Debug).opacity, 1.0);
+    assert_eq!(stack.state(Layer::Roads).opacity, 1.0);
+    assert!(stack.is_visible(Layer::Roads));
+}
@@ -0,0 +1,109 @@
+//! Seed-derived city attributes
+//!
+//! Everything here is a pure function of the 30-bit city seed, using only 32-bit
+//! wrapping integer ops so the Dart client and the AO Lua process can port it verbatim.
+
+use wasm_bindgen::prelude::*;
+
+use crate::hash::mix32;
+
+/// Syllables used to build city names
+const SYLLABLES: [&str; 32] = [
+    "ka", "lo", "mi", "ra", "ven", "tor", "sa", "del", "an", "bri", "cor", "du", "el", "fa",
+    "gan", "hal", "is", "jor", "kel", "lin", "mar", "nor", "os", "pel", "quin", "ros", "sil",
+    "tan", "ul", "vor", "wen", "zel",
+];
+
+/// Salts for independent attribute streams
+const SALT_NAME: u32 = 0x6E61_6D65;
+const SALT_SIZE: u32 = 0x7369_7A65;
+const SALT_POPULATION: u32 = 0x706F_7075;
+const SALT_ERA: u32 = 0x6572_6121;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SizeClass {
+    Village = 0,
+    Town = 1,
+    Metropolis = 2,
+}
+
+impl SizeClass {
+    /// Inclusive population range for this class
+    pub fn population_range(&self) -> (u32, u32) {
+        match self {
+            SizeClass::Village => (50, 1_999),
+            SizeClass::Town => (2_000, 49_999),
+            SizeClass::Metropolis => (50_000, 4_999_999),
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FoundingEra {
+    Ancient = 0,
+    Medieval = 1,
+    Industrial = 2,
+    Modern = 3,
+}
+
+/// Gameplay identity of a city
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CityAttributes {
+    pub name: String,
+    pub population: u32,
+    pub size_class: SizeClass,
+    pub founding_era: FoundingEra,
+}
+
+/// Independent hash stream `index` for a seed and salt
+fn stream(seed: u32, salt: u32, index: u32) -> u32 {
+    mix32(mix32(seed ^ salt).wrapping_add(index))
+}
+
+impl CityAttributes {
+    /// Expand a city seed into its attributes
+    pub fn from_seed(seed: u32) -> Self {
+        let size_class = match stream(seed, SALT_SIZE, 0) % 100 {
+            0..=69 => SizeClass::Village,
+            70..=94 => SizeClass::Town,
+            _ => SizeClass::Metropolis,
+        };
+
+        // Square the unit roll so small populations are more common within each class
+        let (min, max) = size_class.population_range();
+        let roll = (stream(seed, SALT_POPULATION, 0) % 1_000) as u64;
+        let population = min + ((max - min) as u64 * roll * roll / 999_000) as u32;
+
+        let founding_era = match stream(seed, SALT_ERA, 0) % 100 {
+            0..=14 => FoundingEra::Ancient,
+            15..=49 => FoundingEra::Medieval,
+            50..=79 => FoundingEra::Industrial,
+            _ => FoundingEra::Modern,
+        };
+
+        Self {
+            name: city_name(seed),
+            population,
+            size_class,
+            founding_era,
+        }
+    }
+}
+
+/// Pronounceable 2-3 syllable name, capitalized
+pub fn city_name(seed: u32) -> String {
+    let syllables = 2 + stream(seed, SALT_NAME, 0) % 2;
+    let mut name = String::new();
+    for i in 0..syllables {
+        let syllable = SYLLABLES[(stream(seed, SALT_NAME, i + 1) % SYLLABLES.len() as u32) as usize];
+        name.push_str(syllable);
+    }
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => name,
+    }
+}
@@ -1,16 +1,75 @@
-//! Chunk generation and caching
+//! Chunk data and caching
 //! 
-//! Deterministic procedural generation matching the original Dart implementation.
+//! Chunks come from a `ChunkGenerator`; the standard one is deterministic, and
+//! its version 1 matches the original Dart implementation exactly.
 
-use rand::SeedableRng;
-use rand::Rng;
-use rand_pcg::Pcg32;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
 
+use crate::attributes::CityAttributes;
+use crate::config::{ConfigError, WorldConfig, MIN_CACHE_CAPACITY};
+use crate::generator::{ChunkGenerator, StandardGenerator};
+use crate::hydrology::River;
+use crate::lru::Lru;
+use crate::poi::PointOfInterest;
+use crate::region::{RegionCoord, RegionData};
+use crate::terrain::TerrainGrid;
+use crate::version::GeneratorVersion;
+
+/// Default chunk side, in cells (see `WorldConfig`)
 pub const CHUNK_SIZE: i32 = 64;
+/// Default city density
 pub const CITY_DENSITY: f64 = 0.02;
+/// Default cache capacity, in chunks
 pub const MAX_CACHED_CHUNKS: usize = 100;
 
+/// Default Poisson-disc separation, in cells
+pub const DEFAULT_MIN_DISTANCE: f64 = 3.0;
+
+/// How cities are scattered within a chunk
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlacementStrategy {
+    /// Uniform random cells with duplicate rejection
+    Uniform,
+    /// Blue noise: no two cities closer than `min_distance` cells, across chunk borders
+    #[serde(rename = "blue_noise")]
+    PoissonDisc { min_distance: f64 },
+}
+
+impl PlacementStrategy {
+    /// Parse a strategy name ("uniform", "blue_noise"); `min_distance` is used by
+    /// blue noise and must be finite and positive
+    pub fn from_name(name: &str, min_distance: f64) -> Result<Self, ConfigError> {
+        let strategy = match name {
+            "uniform" => PlacementStrategy::Uniform,
+            "blue_noise" | "poisson" => PlacementStrategy::PoissonDisc { min_distance },
+            _ => return Err(ConfigError::UnknownPlacement(name.to_string())),
+        };
+        strategy.validate(f64::MAX)?;
+        Ok(strategy)
+    }
+
+    /// Check the separation is positive and at most `max_distance` (rejects NaN)
+    pub fn validate(&self, max_distance: f64) -> Result<(), ConfigError> {
+        match *self {
+            PlacementStrategy::PoissonDisc { min_distance }
+                if !(min_distance > 0.0 && min_distance <= max_distance) =>
+            {
+                Err(ConfigError::MinDistance(min_distance))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Guaranteed distance between cities, in cells; 0 when cities only avoid sharing a cell
+    pub fn min_separation(&self) -> f64 {
+        match *self {
+            PlacementStrategy::Uniform => 0.0,
+            PlacementStrategy::PoissonDisc { min_distance } => min_distance,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct ChunkCoord {
     pub x: i32,
@@ -21,6 +80,32 @@ impl ChunkCoord {
     pub fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
+    
+    /// Chunk holding a world cell
+    pub fn containing(world_x: i32, world_y: i32, chunk_size: i32) -> Self {
+        Self::new(world_x.div_euclid(chunk_size), world_y.div_euclid(chunk_size))
+    }
+    
+    /// World cell of the chunk's top-left corner. Cell-level features (terrain,
+    /// water, regions) live in i32 cells, so they are only defined for chunks
+    /// whose cells fit in i32; beyond that the origin wraps instead of panicking.
+    pub fn origin(&self, chunk_size: i32) -> (i32, i32) {
+        (self.x.wrapping_mul(chunk_size), self.y.wrapping_mul(chunk_size))
+    }
+
+    /// Whether every cell of the chunk fits in i32, so cell-level features are defined
+    pub fn cells_in_range(&self, chunk_size: i32) -> bool {
+        let (origin_x, origin_y) = self.world_origin(chunk_size);
+        let cells = i32::MIN as i64..=i32::MAX as i64;
+        [origin_x, origin_y]
+            .iter()
+            .all(|&o| cells.contains(&o) && cells.contains(&(o + chunk_size as i64 - 1)))
+    }
+
+    /// Exact world cell of the chunk's top-left corner, for any chunk
+    pub fn world_origin(&self, chunk_size: i32) -> (i64, i64) {
+        (self.x as i64 * chunk_size as i64, self.y as i64 * chunk_size as i64)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -31,117 +116,235 @@ pub struct City {
 }
 
 impl City {
+    /// World cell of the city in a chunk of `chunk_size` cells
+    pub fn world_position(&self, chunk: &ChunkCoord, chunk_size: i32) -> (f64, f64) {
+        let (origin_x, origin_y) = chunk.world_origin(chunk_size);
+        (
+            (origin_x + self.grid_x as i64) as f64,
+            (origin_y + self.grid_y as i64) as f64,
+        )
+    }
+    
+    /// Normalized position within a chunk of `chunk_size` cells (0.0-1.0)
+    pub fn local_position(&self, chunk_size: i32) -> (f64, f64) {
+        let size = chunk_size as f64;
+        (self.grid_x as f64 / size, self.grid_y as f64 / size)
+    }
+    
     /// Get world X coordinate
+    #[deprecated(note = "assumes CHUNK_SIZE; use `world_position` with the world's chunk size")]
     pub fn world_x(&self, chunk: &ChunkCoord) -> f64 {
-        (chunk.x * CHUNK_SIZE + self.grid_x) as f64
+        self.world_position(chunk, CHUNK_SIZE).0
     }
     
-    /// Get world Y coordinate  
+    /// Get world Y coordinate
+    #[deprecated(note = "assumes CHUNK_SIZE; use `world_position` with the world's chunk size")]
     pub fn world_y(&self, chunk: &ChunkCoord) -> f64 {
-        (chunk.y * CHUNK_SIZE + self.grid_y) as f64
+        self.world_position(chunk, CHUNK_SIZE).1
     }
     
     /// Get normalized position within chunk (0.0-1.0)
+    #[deprecated(note = "assumes CHUNK_SIZE; use `local_position` with the world's chunk size")]
     pub fn local_x(&self) -> f64 {
-        self.grid_x as f64 / CHUNK_SIZE as f64
+        self.local_position(CHUNK_SIZE).0
     }
     
+    #[deprecated(note = "assumes CHUNK_SIZE; use `local_position` with the world's chunk size")]
     pub fn local_y(&self) -> f64 {
-        self.grid_y as f64 / CHUNK_SIZE as f64
+        self.local_position(CHUNK_SIZE).1
+    }
+    
+    /// Derive name, population, size class and founding era from the seed
+    pub fn attributes(&self) -> CityAttributes {
+        CityAttributes::from_seed(self.seed)
     }
 }
 
 pub struct ChunkData {
     pub coord: ChunkCoord,
+    /// Cells per chunk side
+    pub size: i32,
     pub cities: Vec<City>,
+    /// Frame of the most recent `get_or_generate`
     pub last_used: u64,
+    /// Frame in which this chunk was generated
+    pub generated_at: u64,
+    /// Biome of every cell in the chunk, lakes included
+    pub terrain: TerrainGrid,
+    /// Rivers passing through the chunk, in full
+    pub rivers: Vec<River>,
+    /// Enclosing super-chunk region
+    pub region: RegionCoord,
+    /// Depots, markets, landmarks and hazards
+    pub pois: Vec<PointOfInterest>,
+}
+
+/// Lookup counters of a `ChunkCache`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// `get_or_generate` calls served from the cache
+    pub hits: u64,
+    /// `get_or_generate` calls that generated the chunk
+    pub misses: u64,
+    /// Chunks dropped to stay within capacity or by a placement change
+    pub evictions: u64,
 }
 
-pub struct ChunkCache {
-    world_seed: u32,
-    cache: HashMap<ChunkCoord, ChunkData>,
+impl CacheStats {
+    /// Fraction of lookups served from the cache, 0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Memoises a chunk generator, evicting least recently used chunks
+pub struct ChunkCache<G = StandardGenerator> {
+    generator: G,
+    capacity: usize,
+    cache: Lru<ChunkCoord, ChunkData>,
     frame_counter: u64,
+    /// Chunks evicted since the last `advance_frame`
+    evicted: Vec<ChunkCoord>,
+    stats: CacheStats,
 }
 
 impl ChunkCache {
+    /// Cache over the default world of a 32-bit seed; use `with_config` for
+    /// 64-bit seeds
     pub fn new(world_seed: u32) -> Self {
-        Self {
+        Self::with_config(u64::from(world_seed), WorldConfig::default())
+            .expect("default config is valid")
+    }
+    
+    /// Create a cache that places cities with the given strategy
+    pub fn with_strategy(
+        world_seed: u64,
+        strategy: PlacementStrategy,
+    ) -> Result<Self, ConfigError> {
+        Self::with_config(
             world_seed,
-            cache: HashMap::new(),
+            WorldConfig {
+                placement: strategy,
+                ..WorldConfig::default()
+            },
+        )
+    }
+    
+    /// Create a cache following a world configuration
+    pub fn with_config(world_seed: u64, config: WorldConfig) -> Result<Self, ConfigError> {
+        let mut cache = Self::with_generator(StandardGenerator::new(world_seed, config)?);
+        cache.capacity = config.cache_capacity;
+        Ok(cache)
+    }
+    
+    /// World configuration in use, with the current cache capacity
+    pub fn config(&self) -> WorldConfig {
+        WorldConfig {
+            cache_capacity: self.capacity,
+            ..*self.generator.config()
+        }
+    }
+    
+    /// Generator version in use
+    pub fn version(&self) -> GeneratorVersion {
+        self.generator.version()
+    }
+    
+    /// Current placement strategy
+    pub fn strategy(&self) -> PlacementStrategy {
+        self.generator.config().placement
+    }
+    
+    /// Switch placement strategy, dropping all cached chunks. The strategy must
+    /// pass `WorldConfig::validate` for this world.
+    pub fn set_strategy(&mut self, strategy: PlacementStrategy) -> Result<(), ConfigError> {
+        if strategy != self.strategy() {
+            self.generator.set_placement(strategy)?;
+            let before = self.evicted.len();
+            self.evicted.extend(self.cache.clear());
+            self.stats.evictions += (self.evicted.len() - before) as u64;
+        }
+        Ok(())
+    }
+}
+
+impl<G: ChunkGenerator> ChunkCache<G> {
+    /// Create a cache over any generator, holding `MAX_CACHED_CHUNKS` chunks
+    pub fn with_generator(generator: G) -> Self {
+        Self {
+            generator,
+            capacity: MAX_CACHED_CHUNKS,
+            cache: Lru::new(),
             frame_counter: 0,
+            evicted: Vec::new(),
+            stats: CacheStats::default(),
         }
     }
     
-    /// Get or generate a chunk
+    /// Get or generate a chunk, marking it most recently used
     pub fn get_or_generate(&mut self, coord: ChunkCoord) -> &ChunkData {
-        if !self.cache.contains_key(&coord) {
-            let data = self.generate_chunk(coord);
+        if self.cache.contains(&coord) {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+            let mut data = self.generator.generate(coord);
+            data.generated_at = self.frame_counter;
+            // Inserted as most recently used, so never evicted right away
             self.cache.insert(coord, data);
             self.evict_if_needed();
         }
         
-        // Update LRU timestamp
-        if let Some(chunk) = self.cache.get_mut(&coord) {
-            chunk.last_used = self.frame_counter;
-        }
-        
-        self.cache.get(&coord).unwrap()
+        let frame = self.frame_counter;
+        let chunk = self.cache.touch(&coord).expect("chunk is cached");
+        chunk.last_used = frame;
+        chunk
     }
     
-    /// Generate chunk - MUST match Dart algorithm exactly!
-    fn generate_chunk(&self, coord: ChunkCoord) -> ChunkData {
-        // Dart: worldSeed ^ (coord.x * 73856093) ^ (coord.y * 19349663)
-        let chunk_seed = (self.world_seed as i64)
-            ^ ((coord.x as i64).wrapping_mul(73856093))
-            ^ ((coord.y as i64).wrapping_mul(19349663));
-        
-        let mut rng = Pcg32::seed_from_u64(chunk_seed as u64);
-        
-        let num_cells = (CHUNK_SIZE * CHUNK_SIZE) as usize;
-        let expected_cities = (num_cells as f64 * CITY_DENSITY).round() as usize;
-        
-        let mut cities = Vec::with_capacity(expected_cities);
-        let mut used_positions = std::collections::HashSet::new();
-        
-        for _ in 0..expected_cities {
-            // Dart: rng.nextInt(chunkSize)
-            let grid_x = rng.gen_range(0..CHUNK_SIZE);
-            let grid_y = rng.gen_range(0..CHUNK_SIZE);
-            let pos_key = grid_y * CHUNK_SIZE + grid_x;
-            
-            // Skip if position already used
-            if used_positions.contains(&pos_key) {
-                continue;
-            }
-            used_positions.insert(pos_key);
-            
-            // Dart: rng.nextInt(1 << 30)
-            let seed = rng.gen::<u32>() & 0x3FFFFFFF;
-            cities.push(City { grid_x, grid_y, seed });
-        }
-        
-        ChunkData {
-            coord,
-            cities,
-            last_used: self.frame_counter,
-        }
+    /// Generator behind this cache
+    pub fn generator(&self) -> &G {
+        &self.generator
     }
     
-    /// Evict oldest chunks if over limit
+    /// Cells per chunk side
+    pub fn chunk_size(&self) -> i32 {
+        self.generator.chunk_size()
+    }
+    
+    /// Macro structure of a super-chunk region
+    pub fn region_data(&self, region: RegionCoord) -> RegionData {
+        self.generator.region_data(region)
+    }
+    
+    /// Most chunks kept before the least recently used are evicted
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    
+    /// Change the capacity (at least `MIN_CACHE_CAPACITY`), evicting least
+    /// recently used chunks if over it
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(MIN_CACHE_CAPACITY);
+        self.evict_if_needed();
+    }
+    
+    /// Get a cached chunk without generating it or changing its recency
+    pub fn get(&self, coord: ChunkCoord) -> Option<&ChunkData> {
+        self.cache.peek(&coord)
+    }
+    
+    /// Evict least recently used chunks while over capacity
     fn evict_if_needed(&mut self) {
-        if self.cache.len() <= MAX_CACHED_CHUNKS {
-            return;
-        }
-        
-        // Sort by last_used, remove oldest
-        let mut entries: Vec<_> = self.cache.iter()
-            .map(|(k, v)| (*k, v.last_used))
-            .collect();
-        entries.sort_by_key(|(_, t)| *t);
-        
-        let to_remove = self.cache.len() - MAX_CACHED_CHUNKS;
-        for (coord, _) in entries.into_iter().take(to_remove) {
-            self.cache.remove(&coord);
+        while self.cache.len() > self.capacity {
+            let Some((coord, _)) = self.cache.pop_oldest() else {
+                break;
+            };
+            self.evicted.push(coord);
+            self.stats.evictions += 1;
         }
     }
     
@@ -155,7 +358,7 @@ impl ChunkCache {
         viewport_height: f64,
     ) -> Vec<ChunkCoord> {
         let cell_size = zoom;
-        // let chunk_pixel_size = CHUNK_SIZE as f64 * cell_size; // Unused
+        let chunk_size = self.chunk_size() as f64;
         
         // Viewport in world coordinates
         let view_left = camera_x;
@@ -164,10 +367,10 @@ impl ChunkCache {
         let view_bottom = camera_y + viewport_height / cell_size;
         
         // Chunk bounds
-        let min_cx = (view_left / CHUNK_SIZE as f64).floor() as i32;
-        let max_cx = (view_right / CHUNK_SIZE as f64).ceil() as i32;
-        let min_cy = (view_top / CHUNK_SIZE as f64).floor() as i32;
-        let max_cy = (view_bottom / CHUNK_SIZE as f64).ceil() as i32;
+        let min_cx = (view_left / chunk_size).floor() as i32;
+        let max_cx = (view_right / chunk_size).ceil() as i32;
+        let min_cy = (view_top / chunk_size).floor() as i32;
+        let max_cy = (view_bottom / chunk_size).ceil() as i32;
         
         let mut chunks = Vec::new();
         for cx in min_cx..=max_cx {
@@ -178,9 +381,20 @@ impl ChunkCache {
         chunks
     }
     
-    /// Advance frame counter for LRU
+    /// Advance the frame counter and start a fresh eviction list
     pub fn advance_frame(&mut self) {
         self.frame_counter += 1;
+        self.evicted.clear();
+    }
+    
+    /// Current frame number
+    pub fn frame(&self) -> u64 {
+        self.frame_counter
+    }
+    
+    /// Chunks evicted during the current frame
+    pub fn evicted_this_frame(&self) -> &[ChunkCoord] {
+        &self.evicted
     }
     
     /// Get cached chunk count
@@ -188,6 +402,15 @@ impl ChunkCache {
         self.cache.len()
     }
     
+    /// Hits, misses and evictions since creation or the last `reset_stats`
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+    
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+    
     /// Iterate over all cached chunks
     pub fn iter(&self) -> impl Iterator<Item = &ChunkData> {
         self.cache.values()
@@ -0,0 +1,175 @@
+//! World ruleset configuration
+//!
+//! A `WorldConfig` bundles the parameters that used to be compile-time
+//! constants. Together with the seed, everything except the cache capacity
+//! decides what the world looks like, and `ruleset_hash` identifies exactly
+//! that part so clients can tell whether two worlds follow the same rules.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::chunk::{PlacementStrategy, CHUNK_SIZE, CITY_DENSITY, MAX_CACHED_CHUNKS};
+use crate::version::GeneratorVersion;
+
+/// Smallest supported chunk side, in cells. Region features need the room.
+pub const MIN_CHUNK_SIZE: i32 = 32;
+/// Largest supported chunk side, in cells
+pub const MAX_CHUNK_SIZE: i32 = 256;
+/// Highest supported city density (cities per cell before thinning)
+pub const MAX_CITY_DENSITY: f64 = 0.25;
+/// Roads and territories look at the 3x3 chunk neighbourhood, which must fit in the cache
+pub const MIN_CACHE_CAPACITY: usize = 9;
+
+/// Why a configuration was rejected
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    ChunkSize(i32),
+    CityDensity(f64),
+    MinDistance(f64),
+    /// Not a placement strategy name
+    UnknownPlacement(String),
+    CacheCapacity(usize),
+    GeneratorVersion(u32),
+    /// The generator version only supports uniform placement
+    Placement(u32),
+    Parse(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ChunkSize(size) => write!(
+                f,
+                "chunk size {} outside {}..={}",
+                size, MIN_CHUNK_SIZE, MAX_CHUNK_SIZE
+            ),
+            ConfigError::CityDensity(density) => write!(
+                f,
+                "city density {} outside (0, {}]",
+                density, MAX_CITY_DENSITY
+            ),
+            ConfigError::MinDistance(distance) => write!(
+                f,
+                "minimum distance {} outside (0, chunk size]",
+                distance
+            ),
+            ConfigError::UnknownPlacement(name) => {
+                write!(f, "unknown placement strategy {}", name)
+            }
+            ConfigError::CacheCapacity(capacity) => write!(
+                f,
+                "cache capacity {} below {}",
+                capacity, MIN_CACHE_CAPACITY
+            ),
+            ConfigError::GeneratorVersion(version) => {
+                write!(f, "unsupported generator version {}", version)
+            }
+            ConfigError::Placement(version) => {
+                let layered = GeneratorVersion::ALL
+                    .into_iter()
+                    .find(|v| v.supports_placement())
+                    .map_or(0, |v| v.number());
+                write!(
+                    f,
+                    "generator version {} only supports uniform placement; other strategies \
+                     need a config with generator_version {} or later",
+                    version, layered
+                )
+            }
+            ConfigError::Parse(message) => write!(f, "invalid config: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldConfig {
+    /// Cells per chunk side
+    pub chunk_size: i32,
+    /// Average cities per cell before terrain and density thinning
+    pub city_density: f64,
+    pub placement: PlacementStrategy,
+    /// Chunks kept in a `ChunkCache`
+    pub cache_capacity: usize,
+    /// Number of a `GeneratorVersion`
+    pub generator_version: u32,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            chunk_size: CHUNK_SIZE,
+            city_density: CITY_DENSITY,
+            placement: PlacementStrategy::Uniform,
+            cache_capacity: MAX_CACHED_CHUNKS,
+            // Existing entry points keep producing v1 worlds; newer versions are opt-in
+            generator_version: GeneratorVersion::DartScatter.number(),
+        }
+    }
+}
+
+impl WorldConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if !(MIN_CHUNK_SIZE..=MAX_CHUNK_SIZE).contains(&self.chunk_size) {
+            return Err(ConfigError::ChunkSize(self.chunk_size));
+        }
+        // Also rejects NaN
+        if !(self.city_density > 0.0 && self.city_density <= MAX_CITY_DENSITY) {
+            return Err(ConfigError::CityDensity(self.city_density));
+        }
+        self.placement.validate(self.chunk_size as f64)?;
+        if self.cache_capacity < MIN_CACHE_CAPACITY {
+            return Err(ConfigError::CacheCapacity(self.cache_capacity));
+        }
+        let version = self.version()?;
+        if !version.supports_placement() && self.placement != PlacementStrategy::Uniform {
+            return Err(ConfigError::Placement(self.generator_version));
+        }
+        Ok(())
+    }
+
+    /// Registered generator version this config selects
+    pub fn version(&self) -> Result<GeneratorVersion, ConfigError> {
+        GeneratorVersion::from_number(self.generator_version)
+            .ok_or(ConfigError::GeneratorVersion(self.generator_version))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("config serializes")
+    }
+
+    /// Parse and validate; missing fields take their defaults
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        let config: Self =
+            serde_json::from_str(json).map_err(|e| ConfigError::Parse(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Stable 64-bit FNV-1a hash of the ruleset: every field that affects
+    /// generated content, in a fixed little-endian layout. The cache capacity
+    /// is left out since it never changes the world.
+    pub fn ruleset_hash(&self) -> u64 {
+        let mut bytes = Vec::with_capacity(32);
+        bytes.extend_from_slice(&self.generator_version.to_le_bytes());
+        bytes.extend_from_slice(&self.chunk_size.to_le_bytes());
+        bytes.extend_from_slice(&self.city_density.to_bits().to_le_bytes());
+        match self.placement {
+            PlacementStrategy::Uniform => bytes.push(0),
+            PlacementStrategy::PoissonDisc { min_distance } => {
+                bytes.push(1);
+                bytes.extend_from_slice(&min_distance.to_bits().to_le_bytes());
+            }
+        }
+        bytes.iter().fold(0xCBF2_9CE4_8422_2325, |hash, &b| {
+            (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3)
+        })
+    }
+
+    /// `ruleset_hash` as 16 hex digits
+    pub fn ruleset_id(&self) -> String {
+        format!("{:016x}", self.ruleset_hash())
+    }
+}
@@ -0,0 +1,55 @@
+//! City density field
+//!
+//! A low-frequency field that modulates how many cities survive in each area:
+//! dense heartlands, sparse frontiers and empty wastes.
+
+use crate::hash::{hash2, unit_f64};
+use crate::noise::{fbm, smoothstep};
+use crate::seed::field_seed;
+
+const SALT_DENSITY: u32 = 0x4445_4E53;
+const SALT_THINNING: u32 = 0x5448_494E;
+
+/// Wavelength of the density field, in cells (several chunks per feature)
+const WAVELENGTH: f64 = 512.0;
+const OCTAVES: u32 = 3;
+
+/// Field values below this are empty wastes, above `HEARTLAND_LEVEL` fully dense
+const WASTE_LEVEL: f64 = 0.32;
+const HEARTLAND_LEVEL: f64 = 0.68;
+
+/// Peak density relative to `CITY_DENSITY`; the field averages out near 1x
+pub const MAX_DENSITY_FACTOR: f64 = 2.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct DensityField {
+    field_seed: u64,
+    thinning_seed: u64,
+}
+
+impl DensityField {
+    pub fn new(world_seed: u64) -> Self {
+        Self {
+            field_seed: field_seed(world_seed, SALT_DENSITY),
+            thinning_seed: field_seed(world_seed, SALT_THINNING),
+        }
+    }
+
+    /// Fraction of peak density at a world position, in [0.0, 1.0]
+    pub fn keep_probability(&self, x: f64, y: f64) -> f64 {
+        let v = fbm(self.field_seed, x, y, WAVELENGTH, OCTAVES);
+        smoothstep(WASTE_LEVEL, HEARTLAND_LEVEL, v)
+    }
+
+    /// Local density multiplier applied to `CITY_DENSITY`
+    pub fn factor(&self, x: f64, y: f64) -> f64 {
+        self.keep_probability(x, y) * MAX_DENSITY_FACTOR
+    }
+
+    /// Whether a candidate city in this world cell survives thinning.
+    /// Hash-based rather than RNG-based so it never perturbs the placement stream.
+    pub fn keeps(&self, world_x: i32, world_y: i32) -> bool {
+        let p = self.keep_probability(world_x as f64 + 0.5, world_y as f64 + 0.5);
+        unit_f64(hash2(self.thinning_seed, world_x, world_y)) < p
+    }
+}
@@ -0,0 +1,550 @@
+//! Chunk generators
+//!
+//! A `ChunkGenerator` turns a chunk coordinate into its contents. `ChunkCache`
+//! memoises any generator, and tools can call one directly without a cache;
+//! `generate` covers the common one-off case.
+//! `StandardGenerator` is the world generator proper; `FixtureGenerator` and
+//! `CompositeGenerator` cover hand-built and layered worlds.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::chunk::{ChunkCoord, ChunkData, City, PlacementStrategy};
+use crate::config::{ConfigError, WorldConfig};
+use crate::density::{DensityField, MAX_DENSITY_FACTOR};
+use crate::hydrology::{Hydrology, BANK_DISTANCE};
+use crate::lru::Lru;
+use crate::poi::place_pois;
+use crate::region::{RegionCoord, RegionData};
+use crate::rng::Pcg32;
+use crate::terrain::{Biome, TerrainField, TerrainGrid};
+use crate::version::{GeneratorVersion, SeedLayer};
+
+/// Poisson-disc candidates per chunk, relative to the uniform candidate count
+const POISSON_OVERSAMPLE: usize = 2;
+
+/// Regions kept for reuse; each chunk reads its own and up to 3 neighbours
+const REGION_CACHE_CAPACITY: usize = 64;
+
+/// Produces the contents of chunks
+pub trait ChunkGenerator {
+    /// Cells per chunk side
+    fn chunk_size(&self) -> i32;
+
+    /// Contents of a chunk. Must depend on the coordinate alone, since caches
+    /// drop and regenerate chunks at any time. Cache bookkeeping fields are
+    /// left at 0 for the cache to fill in.
+    fn generate(&self, coord: ChunkCoord) -> ChunkData;
+
+    /// Macro structure of a super-chunk region; featureless by default
+    fn region_data(&self, region: RegionCoord) -> RegionData {
+        RegionData::featureless(0, region)
+    }
+}
+
+/// Generate one chunk of a world with the default configuration, without a
+/// cache. Use `StandardGenerator` for other configurations or many chunks.
+pub fn generate(world_seed: u64, coord: ChunkCoord) -> ChunkData {
+    StandardGenerator::new(world_seed, WorldConfig::default())
+        .expect("default config is valid")
+        .generate(coord)
+}
+
+/// Blue-noise candidate before separation is enforced
+struct Candidate {
+    grid_x: i32,
+    grid_y: i32,
+    seed: u32,
+    priority: u32,
+}
+
+impl Candidate {
+    /// Total order for conflict resolution; position breaks priority ties
+    fn rank(&self, chunk: ChunkCoord, chunk_size: i32) -> (u32, i64, i64) {
+        let world_x = chunk.x as i64 * chunk_size as i64 + self.grid_x as i64;
+        let world_y = chunk.y as i64 * chunk_size as i64 + self.grid_y as i64;
+        (self.priority, world_x, world_y)
+    }
+}
+
+/// The world generator: every registered generator version, driven by a `WorldConfig`
+pub struct StandardGenerator {
+    world_seed: u64,
+    config: WorldConfig,
+    version: GeneratorVersion,
+    terrain: TerrainField,
+    density: DensityField,
+    hydrology: Hydrology,
+    /// Regions of the layered versions by coordinate; every chunk of a region
+    /// and its neighbours reads the same one
+    regions: RefCell<Lru<RegionCoord, RegionData>>,
+}
+
+impl StandardGenerator {
+    /// Generator following a validated world configuration
+    pub fn new(world_seed: u64, config: WorldConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        Ok(Self {
+            world_seed,
+            config,
+            version: config.version()?,
+            terrain: TerrainField::new(world_seed),
+            density: DensityField::new(world_seed),
+            hydrology: Hydrology::new(world_seed, config.chunk_size),
+            regions: RefCell::new(Lru::new()),
+        })
+    }
+
+    pub fn world_seed(&self) -> u64 {
+        self.world_seed
+    }
+
+    /// World configuration in use
+    pub fn config(&self) -> &WorldConfig {
+        &self.config
+    }
+
+    /// Generator version in use
+    pub fn version(&self) -> GeneratorVersion {
+        self.version
+    }
+
+    /// Switch placement strategy; chunks generated before no longer match.
+    /// The strategy must pass `WorldConfig::validate` for this world.
+    pub fn set_placement(&mut self, strategy: PlacementStrategy) -> Result<(), ConfigError> {
+        let config = WorldConfig {
+            placement: strategy,
+            ..self.config
+        };
+        config.validate()?;
+        self.config = config;
+        // Hub spacing follows the placement separation
+        *self.regions.borrow_mut() = Lru::new();
+        Ok(())
+    }
+
+    /// Version 1 - MUST match Dart algorithm exactly!
+    /// Uniform scatter over a featureless world; the placement strategy is ignored.
+    fn generate_dart_scatter(&self, coord: ChunkCoord) -> ChunkData {
+        let size = self.config.chunk_size;
+        let seed = self
+            .version
+            .chunk_seed(self.world_seed, coord, SeedLayer::Cities);
+        let mut rng = Pcg32::from_seed(seed);
+
+        let num_cells = (size * size) as usize;
+        let expected_cities = (num_cells as f64 * self.config.city_density).round() as usize;
+
+        let mut cities = Vec::with_capacity(expected_cities);
+        let mut used_positions = std::collections::HashSet::new();
+
+        for _ in 0..expected_cities {
+            // Dart: rng.nextInt(chunkSize)
+            let grid_x = rng.range(0, size);
+            let grid_y = rng.range(0, size);
+            let pos_key = grid_y * size + grid_x;
+
+            // Skip if position already used
+            if used_positions.contains(&pos_key) {
+                continue;
+            }
+            used_positions.insert(pos_key);
+
+            // Dart: rng.nextInt(1 << 30)
+            let seed = rng.next_u32() & 0x3FFFFFFF;
+            cities.push(City {
+                grid_x,
+                grid_y,
+                seed,
+            });
+        }
+
+        ChunkData {
+            coord,
+            size,
+            cities,
+            terrain: TerrainGrid::uniform(size, Biome::Plains),
+            rivers: Vec::new(),
+            region: RegionCoord::from_chunk(coord),
+            pois: Vec::new(),
+            last_used: 0,
+            generated_at: 0,
+        }
+    }
+
+    /// Versions 2 and 3: candidates are scattered at peak density, then thinned by the
+    /// density field and terrain using position hashes, so the RNG stream stays linear.
+    /// Chunks whose cells fall outside i32 are open ocean without cities, water or POIs.
+    fn generate_layered(&self, coord: ChunkCoord) -> ChunkData {
+        let size = self.config.chunk_size;
+        if !coord.cells_in_range(size) {
+            return ChunkData {
+                coord,
+                size,
+                cities: Vec::new(),
+                terrain: TerrainGrid::uniform(size, Biome::Ocean),
+                rivers: Vec::new(),
+                region: RegionCoord::from_chunk(coord),
+                pois: Vec::new(),
+                last_used: 0,
+                generated_at: 0,
+            };
+        }
+        let mut cities = match self.config.placement {
+            PlacementStrategy::Uniform => self.scatter_uniform(coord),
+            PlacementStrategy::PoissonDisc { min_distance } => {
+                self.scatter_poisson(coord, min_distance)
+            }
+        };
+
+        let (origin_x, origin_y) = coord.origin(size);
+        let in_chunk = |(x, y): (i32, i32)| {
+            let (gx, gy) = (x.wrapping_sub(origin_x), y.wrapping_sub(origin_y));
+            (0..size).contains(&gx) && (0..size).contains(&gy)
+        };
+
+        // Flood lakes into the terrain
+        let mut terrain = self.terrain.chunk_grid(coord, size);
+        let (rivers, lakes) = self.hydrology.water_in_chunk(coord);
+        for &(x, y) in lakes.iter().flat_map(|l| &l.cells) {
+            if in_chunk((x, y)) {
+                terrain.set(
+                    x.wrapping_sub(origin_x),
+                    y.wrapping_sub(origin_y),
+                    Biome::Lake,
+                );
+            }
+        }
+
+        // River cells, and the banks within BANK_DISTANCE of them
+        let mut river_cells = std::collections::HashSet::new();
+        let mut bank_cells = std::collections::HashSet::new();
+        for &(x, y) in rivers.iter().flat_map(|r| &r.points) {
+            river_cells.insert((x, y));
+            for dy in -BANK_DISTANCE..=BANK_DISTANCE {
+                for dx in -BANK_DISTANCE..=BANK_DISTANCE {
+                    bank_cells.insert((x.wrapping_add(dx), y.wrapping_add(dy)));
+                }
+            }
+        }
+
+        // Thin to the local density (banks and hub surroundings are favoured),
+        // clear wilderness and never place cities in water
+        let region = self.region_data(RegionCoord::from_chunk(coord));
+        cities.retain(|c| {
+            let cell = (
+                origin_x.wrapping_add(c.grid_x),
+                origin_y.wrapping_add(c.grid_y),
+            );
+            let kept = self.density.keeps(cell.0, cell.1)
+                || region.near_hub(cell.0, cell.1)
+                || (bank_cells.contains(&cell) && self.hydrology.bank_keeps(cell.0, cell.1));
+            kept && !region.in_wilderness(cell.0, cell.1)
+                && !river_cells.contains(&cell)
+                && !terrain.get(c.grid_x, c.grid_y).is_water()
+        });
+
+        // The capital and trade hubs are always cities; scattered cities give way to
+        // hubs closer than the placement separation, including those across a region
+        // border (separation is at most a chunk, so the 3x3 neighbourhood covers it)
+        let separation = self.config.placement.min_separation();
+        let mut nearby_regions = Vec::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let neighbour = ChunkCoord::new(coord.x.wrapping_add(dx), coord.y.wrapping_add(dy));
+                let other = RegionCoord::from_chunk(neighbour);
+                if other != region.coord && !nearby_regions.contains(&other) {
+                    nearby_regions.push(other);
+                }
+            }
+        }
+        let nearby_hubs: Vec<(i32, i32)> = nearby_regions
+            .into_iter()
+            .flat_map(|other| self.region_data(other).hubs().collect::<Vec<_>>())
+            .chain(region.hubs())
+            .collect();
+        cities.retain(|c| {
+            let x = origin_x as i64 + c.grid_x as i64;
+            let y = origin_y as i64 + c.grid_y as i64;
+            nearby_hubs.iter().all(|&(hx, hy)| {
+                let (dx, dy) = ((hx as i64 - x) as f64, (hy as i64 - y) as f64);
+                (dx, dy) != (0.0, 0.0) && dx * dx + dy * dy >= separation * separation
+            })
+        });
+        for (index, hub) in region.hubs().enumerate() {
+            if in_chunk(hub) {
+                let (grid_x, grid_y) = (hub.0.wrapping_sub(origin_x), hub.1.wrapping_sub(origin_y));
+                cities.push(City {
+                    grid_x,
+                    grid_y,
+                    seed: region.hub_city_seed(index as u32),
+                });
+            }
+        }
+
+        let pois = place_pois(self.world_seed, coord, &terrain, &cities, &rivers);
+
+        ChunkData {
+            coord,
+            size,
+            cities,
+            terrain,
+            rivers,
+            region: region.coord,
+            pois,
+            last_used: 0,
+            generated_at: 0,
+        }
+    }
+
+    /// Number of raw candidates per chunk before thinning
+    fn candidate_count(&self) -> usize {
+        let num_cells = (self.config.chunk_size * self.config.chunk_size) as usize;
+        (num_cells as f64 * self.config.city_density * MAX_DENSITY_FACTOR).round() as usize
+    }
+
+    /// Uniform scatter with duplicate rejection
+    fn scatter_uniform(&self, coord: ChunkCoord) -> Vec<City> {
+        let seed = self
+            .version
+            .chunk_seed(self.world_seed, coord, SeedLayer::Cities);
+        let mut rng = Pcg32::from_seed(seed);
+        let size = self.config.chunk_size;
+        let candidates = self.candidate_count();
+
+        let mut cities = Vec::with_capacity(candidates);
+        let mut used_positions = std::collections::HashSet::new();
+
+        for _ in 0..candidates {
+            // Dart: rng.nextInt(chunkSize)
+            let grid_x = rng.range(0, size);
+            let grid_y = rng.range(0, size);
+            let pos_key = grid_y * size + grid_x;
+
+            // Skip if position already used
+            if used_positions.contains(&pos_key) {
+                continue;
+            }
+            used_positions.insert(pos_key);
+
+            // Dart: rng.nextInt(1 << 30)
+            let seed = rng.next_u32() & 0x3FFFFFFF;
+            cities.push(City {
+                grid_x,
+                grid_y,
+                seed,
+            });
+        }
+        cities
+    }
+
+    /// Raw blue-noise candidates of a chunk, each with a random priority
+    fn poisson_candidates(&self, coord: ChunkCoord) -> Vec<Candidate> {
+        let seed = self
+            .version
+            .chunk_seed(self.world_seed, coord, SeedLayer::PoissonCandidates);
+        let mut rng = Pcg32::from_seed(seed);
+        let size = self.config.chunk_size;
+        let candidates = self.candidate_count() * POISSON_OVERSAMPLE;
+
+        (0..candidates)
+            .map(|_| Candidate {
+                grid_x: rng.range(0, size),
+                grid_y: rng.range(0, size),
+                seed: rng.next_u32() & 0x3FFFFFFF,
+                priority: rng.next_u32(),
+            })
+            .collect()
+    }
+
+    /// Poisson-disc placement (Matern type II): a candidate survives only if it
+    /// outranks every candidate within `min_distance`, including the raw
+    /// candidates of the 8 neighbouring chunks. Neighbour candidates are
+    /// deterministic, so adjacent chunks agree and separation holds across borders.
+    fn scatter_poisson(&self, coord: ChunkCoord, min_distance: f64) -> Vec<City> {
+        // Neighbourhood lookup only covers one chunk in each direction
+        let size = self.config.chunk_size;
+        let min_distance = min_distance.clamp(0.0, size as f64);
+        let min_distance_sq = min_distance * min_distance;
+
+        // Neighbourhood candidates in coordinates relative to this chunk's origin
+        let mut neighbourhood = Vec::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let neighbour = ChunkCoord::new(coord.x.wrapping_add(dx), coord.y.wrapping_add(dy));
+                for c in self.poisson_candidates(neighbour) {
+                    let x = (dx * size + c.grid_x) as f64;
+                    let y = (dy * size + c.grid_y) as f64;
+                    neighbourhood.push((x, y, c.rank(neighbour, size)));
+                }
+            }
+        }
+
+        let mut cities = Vec::new();
+        let mut used_positions = std::collections::HashSet::new();
+        for c in self.poisson_candidates(coord) {
+            let (x, y) = (c.grid_x as f64, c.grid_y as f64);
+            let rank = c.rank(coord, size);
+            let dominated = neighbourhood.iter().any(|&(nx, ny, other)| {
+                other > rank && (nx - x).powi(2) + (ny - y).powi(2) < min_distance_sq
+            });
+            // Candidates tied on priority and position outrank neither; keep one
+            if !dominated && used_positions.insert((c.grid_x, c.grid_y)) {
+                cities.push(City {
+                    grid_x: c.grid_x,
+                    grid_y: c.grid_y,
+                    seed: c.seed,
+                });
+            }
+        }
+        cities
+    }
+}
+
+impl ChunkGenerator for StandardGenerator {
+    fn chunk_size(&self) -> i32 {
+        self.config.chunk_size
+    }
+
+    /// Generate a chunk with the configured generator version
+    fn generate(&self, coord: ChunkCoord) -> ChunkData {
+        match self.version {
+            GeneratorVersion::DartScatter => self.generate_dart_scatter(coord),
+            GeneratorVersion::Layered | GeneratorVersion::HashedSeeds => {
+                self.generate_layered(coord)
+            }
+        }
+    }
+
+    fn region_data(&self, region: RegionCoord) -> RegionData {
+        match self.version {
+            GeneratorVersion::DartScatter => RegionData::featureless(self.world_seed, region),
+            GeneratorVersion::Layered | GeneratorVersion::HashedSeeds => {
+                let mut regions = self.regions.borrow_mut();
+                if !regions.contains(&region) {
+                    let data = RegionData::generate(
+                        self.world_seed,
+                        region,
+                        &self.hydrology,
+                        self.config.chunk_size,
+                        self.config.placement.min_separation(),
+                    );
+                    regions.insert(region, data);
+                    if regions.len() > REGION_CACHE_CAPACITY {
+                        regions.pop_oldest();
+                    }
+                }
+                regions.touch(&region).expect("region is cached").clone()
+            }
+        }
+    }
+}
+
+/// Hand-placed cities on featureless plains, for tests and tools
+#[derive(Clone, Debug)]
+pub struct FixtureGenerator {
+    chunk_size: i32,
+    cities: HashMap<ChunkCoord, Vec<City>>,
+}
+
+impl FixtureGenerator {
+    pub fn new(chunk_size: i32) -> Self {
+        Self {
+            chunk_size,
+            cities: HashMap::new(),
+        }
+    }
+
+    /// Place a city at a world cell, replacing any city already there
+    pub fn with_city(mut self, world_x: i32, world_y: i32, seed: u32) -> Self {
+        let coord = ChunkCoord::containing(world_x, world_y, self.chunk_size);
+        let (origin_x, origin_y) = coord.origin(self.chunk_size);
+        let (grid_x, grid_y) = (
+            world_x.wrapping_sub(origin_x),
+            world_y.wrapping_sub(origin_y),
+        );
+        let cities = self.cities.entry(coord).or_default();
+        cities.retain(|c| (c.grid_x, c.grid_y) != (grid_x, grid_y));
+        cities.push(City {
+            grid_x,
+            grid_y,
+            seed,
+        });
+        self
+    }
+}
+
+impl ChunkGenerator for FixtureGenerator {
+    fn chunk_size(&self) -> i32 {
+        self.chunk_size
+    }
+
+    fn generate(&self, coord: ChunkCoord) -> ChunkData {
+        ChunkData {
+            coord,
+            size: self.chunk_size,
+            cities: self.cities.get(&coord).cloned().unwrap_or_default(),
+            terrain: TerrainGrid::uniform(self.chunk_size, Biome::Plains),
+            rivers: Vec::new(),
+            region: RegionCoord::from_chunk(coord),
+            pois: Vec::new(),
+            last_used: 0,
+            generated_at: 0,
+        }
+    }
+}
+
+/// One stage of a composite generator, refining a chunk in place
+pub trait ChunkLayer {
+    fn apply(&self, chunk: &mut ChunkData);
+}
+
+impl<F: Fn(&mut ChunkData)> ChunkLayer for F {
+    fn apply(&self, chunk: &mut ChunkData) {
+        self(chunk)
+    }
+}
+
+/// A base generator followed by layers applied in order, e.g. custom terrain,
+/// extra cities or points of interest on top of the standard world
+pub struct CompositeGenerator<G> {
+    base: G,
+    layers: Vec<Box<dyn ChunkLayer>>,
+}
+
+impl<G: ChunkGenerator> CompositeGenerator<G> {
+    pub fn new(base: G) -> Self {
+        Self {
+            base,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Append a layer, applied after all earlier ones
+    pub fn with_layer(mut self, layer: impl ChunkLayer + 'static) -> Self {
+        self.layers.push(Box::new(layer));
+        self
+    }
+
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+}
+
+impl<G: ChunkGenerator> ChunkGenerator for CompositeGenerator<G> {
+    fn chunk_size(&self) -> i32 {
+        self.base.chunk_size()
+    }
+
+    fn generate(&self, coord: ChunkCoord) -> ChunkData {
+        let mut chunk = self.base.generate(coord);
+        for layer in &self.layers {
+            layer.apply(&mut chunk);
+        }
+        chunk
+    }
+
+    fn region_data(&self, region: RegionCoord) -> RegionData {
+        self.base.region_data(region)
+    }
+}
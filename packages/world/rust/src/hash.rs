@@ -0,0 +1,41 @@
+//! Portable integer hashing
+//!
+//! Wrapping integer ops only, so every client can reproduce the results exactly.
+
+/// 32-bit integer finalizer (lowbias32)
+pub fn mix32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846C_A68B);
+    x ^= x >> 16;
+    x
+}
+
+/// Hash a 64-bit seed with a 2D integer lattice point, to 32 bits
+pub fn hash2(seed: u64, x: i32, y: i32) -> u32 {
+    let point = (x as u32 as u64) << 32 | y as u32 as u64;
+    (mix64(seed ^ mix64(point.wrapping_add(0x9E37_79B9_7F4A_7C15))) >> 32) as u32
+}
+
+/// 64-bit integer finalizer (SplitMix64)
+pub fn mix64(mut x: u64) -> u64 {
+    x ^= x >> 30;
+    x = x.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x ^= x >> 27;
+    x = x.wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Hash a seed with a 2D lattice point and a layer id. Each input goes through
+/// a full finalizer round, and for a fixed seed and layer distinct points never collide.
+pub fn hash64(seed: u64, x: i32, y: i32, layer: u32) -> u64 {
+    let h = mix64(seed.wrapping_add(0x9E37_79B9_7F4A_7C15));
+    let h = mix64(h ^ ((x as u32 as u64) << 32 | y as u32 as u64));
+    mix64(h ^ layer as u64)
+}
+
+/// Map a hash to [0.0, 1.0)
+pub fn unit_f64(h: u32) -> f64 {
+    h as f64 / 4_294_967_296.0
+}
@@ -0,0 +1,251 @@
+//! Rivers and lakes
+//!
+//! Each chunk may hold one river source in its highlands. A river is traced
+//! downhill on the terrain height field, one cell at a time, until it reaches
+//! the sea or gets stuck in a basin, where it pools into a lake. Traces depend
+//! only on the world seed and the source, so a chunk's water can be found by
+//! tracing the sources within reach instead of generating the whole world.
+
+use crate::chunk::ChunkCoord;
+use crate::hash::{hash2, mix32, unit_f64};
+use crate::lru::Lru;
+use crate::seed::field_seed;
+use crate::terrain::{TerrainField, SEA_LEVEL};
+use std::cell::RefCell;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+const SALT_SOURCE: u32 = 0x5249_5645;
+const SALT_BANK: u32 = 0x4241_4E4B;
+
+/// Sources only start in highlands
+const SOURCE_MIN_HEIGHT: f64 = 0.56;
+/// Chance that a chunk with highland at its candidate cell holds a source
+const SOURCE_PROBABILITY: f64 = 0.5;
+
+/// Longest river, in steps
+pub const MAX_RIVER_LENGTH: usize = 160;
+/// Lakes fill at most this far above the basin floor...
+const MAX_LAKE_DEPTH: f64 = 0.02;
+/// ...but never more than this many cells
+pub const MAX_LAKE_CELLS: usize = 64;
+
+/// Traced sources kept for reuse by the chunks within their reach
+const TRACE_CACHE_CAPACITY: usize = 2048;
+
+/// Cells within this Chebyshev distance of a river count as its banks
+pub const BANK_DISTANCE: i32 = 2;
+/// Chance that a bank city survives even where the density field would thin it
+const BANK_KEEP_PROBABILITY: f64 = 0.75;
+
+/// 8-neighbourhood in a fixed order (ties go to the earliest)
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// A river from source to mouth, in world cells
+#[derive(Clone, Debug, PartialEq)]
+pub struct River {
+    /// Chunk holding the source; unique per river
+    pub source_chunk: ChunkCoord,
+    /// Cells from source to mouth
+    pub points: Vec<(i32, i32)>,
+    /// Whether the river ends in a lake rather than the sea
+    pub ends_in_lake: bool,
+}
+
+/// Water pooled in a basin
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lake {
+    /// Chunk holding the source of the feeding river
+    pub source_chunk: ChunkCoord,
+    /// Flooded cells in world coordinates, basin first
+    pub cells: Vec<(i32, i32)>,
+}
+
+/// River traced from one source chunk, with the lake it ends in
+type Trace = Option<(River, Option<Lake>)>;
+
+/// Deterministic hydrology for a world seed
+#[derive(Clone)]
+pub struct Hydrology {
+    terrain: TerrainField,
+    chunk_size: i32,
+    source_seed: u64,
+    bank_seed: u64,
+    /// Traces by source chunk; every chunk within a source's reach reads the same one
+    traces: RefCell<Lru<ChunkCoord, Trace>>,
+}
+
+impl Hydrology {
+    /// Hydrology with one candidate source per `chunk_size` x `chunk_size` chunk
+    pub fn new(world_seed: u64, chunk_size: i32) -> Self {
+        Self {
+            terrain: TerrainField::new(world_seed),
+            chunk_size,
+            source_seed: field_seed(world_seed, SALT_SOURCE),
+            bank_seed: field_seed(world_seed, SALT_BANK),
+            traces: RefCell::new(Lru::new()),
+        }
+    }
+
+    /// Height at a cell centre
+    fn height(&self, cell: (i32, i32)) -> f64 {
+        self.terrain.height(cell.0 as f64 + 0.5, cell.1 as f64 + 0.5)
+    }
+
+    /// River source cell of a chunk, if it has one
+    pub fn source(&self, chunk: ChunkCoord) -> Option<(i32, i32)> {
+        let h = hash2(self.source_seed, chunk.x, chunk.y);
+        if unit_f64(mix32(h)) >= SOURCE_PROBABILITY {
+            return None;
+        }
+        let (origin_x, origin_y) = chunk.origin(self.chunk_size);
+        let size = self.chunk_size as u32;
+        let cell = (
+            origin_x.wrapping_add((h % size) as i32),
+            origin_y.wrapping_add(((h >> 16) % size) as i32),
+        );
+        (self.height(cell) >= SOURCE_MIN_HEIGHT).then_some(cell)
+    }
+
+    /// Trace the river starting in `chunk`, with the lake it ends in (if any)
+    pub fn river(&self, chunk: ChunkCoord) -> Option<(River, Option<Lake>)> {
+        let mut cell = self.source(chunk)?;
+        let mut height = self.height(cell);
+        let mut points = vec![cell];
+
+        while points.len() < MAX_RIVER_LENGTH && height >= SEA_LEVEL {
+            // Steepest descent
+            let lowest = NEIGHBOURS
+                .iter()
+                .map(|(dx, dy)| (cell.0.wrapping_add(*dx), cell.1.wrapping_add(*dy)))
+                .map(|n| (n, self.height(n)))
+                .fold(None, |best: Option<((i32, i32), f64)>, (n, h)| match best {
+                    Some((_, bh)) if bh <= h => best,
+                    _ => Some((n, h)),
+                });
+            match lowest {
+                Some((next, h)) if h < height => {
+                    cell = next;
+                    height = h;
+                    points.push(cell);
+                }
+                // Basin: pool into a lake
+                _ => {
+                    let lake = self.flood(chunk, cell, height);
+                    let river = River {
+                        source_chunk: chunk,
+                        points,
+                        ends_in_lake: true,
+                    };
+                    return Some((river, Some(lake)));
+                }
+            }
+        }
+
+        let river = River {
+            source_chunk: chunk,
+            points,
+            ends_in_lake: false,
+        };
+        Some((river, None))
+    }
+
+    /// Priority flood from a basin floor: keep adding the lowest shore cell
+    /// until the water would spill out of the basin or the lake is full
+    fn flood(&self, source_chunk: ChunkCoord, basin: (i32, i32), floor: f64) -> Lake {
+        let mut cells = Vec::new();
+        let mut seen = HashSet::new();
+        // Min-heap on height; heights are non-negative so their bits order like the values
+        let mut shore = BinaryHeap::new();
+        seen.insert(basin);
+        shore.push(Reverse((floor.to_bits(), basin)));
+        let mut level = floor;
+
+        while let Some(Reverse((bits, cell))) = shore.pop() {
+            let height = f64::from_bits(bits);
+            if height < level || height > floor + MAX_LAKE_DEPTH {
+                break;
+            }
+            level = height;
+            cells.push(cell);
+            if cells.len() >= MAX_LAKE_CELLS {
+                break;
+            }
+            for (dx, dy) in &NEIGHBOURS[..4] {
+                let n = (cell.0.wrapping_add(*dx), cell.1.wrapping_add(*dy));
+                if seen.insert(n) {
+                    shore.push(Reverse((self.height(n).to_bits(), n)));
+                }
+            }
+        }
+
+        Lake {
+            source_chunk,
+            cells,
+        }
+    }
+
+    /// Whether a bank city in this cell is kept regardless of density
+    pub fn bank_keeps(&self, world_x: i32, world_y: i32) -> bool {
+        unit_f64(hash2(self.bank_seed, world_x, world_y)) < BANK_KEEP_PROBABILITY
+    }
+
+    /// How many chunks away a source can be and still reach a chunk
+    fn source_reach(&self) -> i32 {
+        ((MAX_RIVER_LENGTH + MAX_LAKE_CELLS) as i32 + self.chunk_size - 1) / self.chunk_size
+    }
+
+    /// Rivers passing through, and lakes overlapping, a chunk.
+    /// Traces are memoised per source, so neighbouring chunks share the work.
+    pub fn water_in_chunk(&self, coord: ChunkCoord) -> (Vec<River>, Vec<Lake>) {
+        let in_chunk =
+            |cell: &(i32, i32)| ChunkCoord::containing(cell.0, cell.1, self.chunk_size) == coord;
+
+        let mut traces = self.traces.borrow_mut();
+        let mut rivers = Vec::new();
+        let mut lakes = Vec::new();
+        let reach = self.source_reach();
+        for sy in -reach..=reach {
+            for sx in -reach..=reach {
+                let source = ChunkCoord::new(coord.x.wrapping_add(sx), coord.y.wrapping_add(sy));
+                if !traces.contains(&source) {
+                    traces.insert(source, self.river(source));
+                    if traces.len() > TRACE_CACHE_CAPACITY {
+                        traces.pop_oldest();
+                    }
+                }
+                let Some(Some((river, lake))) = traces.touch(&source) else {
+                    continue;
+                };
+                if river.points.iter().any(in_chunk) {
+                    rivers.push(river.clone());
+                }
+                if let Some(lake) = lake.as_ref().filter(|l| l.cells.iter().any(in_chunk)) {
+                    lakes.push(lake.clone());
+                }
+            }
+        }
+        (rivers, lakes)
+    }
+
+    /// Whether a world cell is sea, lake or river
+    pub fn is_water(&self, world_x: i32, world_y: i32) -> bool {
+        if self.terrain.biome_at(world_x, world_y).is_water() {
+            return true;
+        }
+        let cell = (world_x, world_y);
+        let (rivers, lakes) =
+            self.water_in_chunk(ChunkCoord::containing(world_x, world_y, self.chunk_size));
+        rivers.iter().any(|r| r.points.contains(&cell))
+            || lakes.iter().any(|l| l.cells.contains(&cell))
+    }
+}
@@ -1,8 +1,46 @@
 pub mod chunk;
+pub mod config;
+pub mod generator;
 pub mod camera;
+pub mod attributes;
+pub mod hash;
+pub mod noise;
+pub mod terrain;
+pub mod density;
+pub mod roads;
+pub mod hydrology;
+mod lru;
+pub mod territory;
+pub mod region;
+pub mod poi;
+pub mod query;
+pub mod version;
+pub mod rng;
+pub mod seed;
 
-pub use chunk::{ChunkCache, ChunkCoord, ChunkData, City, CHUNK_SIZE, CITY_DENSITY};
+pub use chunk::{
+    CacheStats, ChunkCache, ChunkCoord, ChunkData, City, PlacementStrategy, CHUNK_SIZE,
+    CITY_DENSITY, DEFAULT_MIN_DISTANCE, MAX_CACHED_CHUNKS,
+};
 pub use camera::Camera;
+pub use config::{ConfigError, WorldConfig};
+pub use generator::{
+    generate, ChunkGenerator, ChunkLayer, CompositeGenerator, FixtureGenerator, StandardGenerator,
+};
+pub use seed::{SeedError, WorldSeed};
+pub use version::{dart_chunk_seed, hashed_chunk_seed, GeneratorVersion, SeedLayer};
+pub use attributes::{CityAttributes, FoundingEra, SizeClass};
+pub use terrain::{Biome, TerrainField, TerrainGrid};
+pub use density::DensityField;
+pub use roads::{max_road_length, roads_touching_chunk, Road};
+pub use hydrology::{Hydrology, Lake, River};
+pub use region::{RegionCoord, RegionData, WildernessZone, REGION_SIZE};
+pub use poi::{PoiKind, PoiProperties, PointOfInterest};
+pub use query::{
+    cities_in_radius, cities_in_rect, city_at, city_near, nearest_cities, verify_cities,
+    CityRecord, QueryError, WorldCity, MAX_QUERY_CHUNKS,
+};
+pub use territory::{territories_for_chunk, Border, Nations, Territories, TerritoryOwner};
 
 use wasm_bindgen::prelude::*;
 
@@ -15,7 +53,8 @@ extern "C" {
 // Re-export for Deno/JS consumption
 #[wasm_bindgen]
 pub struct WorldGenerator {
-    seed: u32,
+    seed: WorldSeed,
+    /// Chunks served by the get_* methods, shared across calls
     chunk_cache: ChunkCache,
 }
 
@@ -24,49 +63,325 @@ impl WorldGenerator {
     #[wasm_bindgen(constructor)]
     pub fn new(seed: u32) -> Self {
         WorldGenerator {
-            seed,
             chunk_cache: ChunkCache::new(seed),
+            seed: WorldSeed::from(seed),
         }
     }
+    
+    /// Create a generator from a JSON `WorldConfig`; missing fields take their defaults
+    pub fn with_config(seed: u32, config_json: &str) -> Result<WorldGenerator, JsValue> {
+        Self::build(WorldSeed::from(seed), config_json)
+    }
+    
+    /// Create a generator from a seed as players type it: a decimal number up to
+    /// 2^64 - 1, or a phrase
+    pub fn from_seed(seed: &str) -> Result<WorldGenerator, JsValue> {
+        Self::from_seed_with_config(seed, "{}")
+    }
+    
+    /// `from_seed` with a JSON `WorldConfig`
+    pub fn from_seed_with_config(seed: &str, config_json: &str) -> Result<WorldGenerator, JsValue> {
+        let seed = WorldSeed::parse(seed).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Self::build(seed, config_json)
+    }
+    
+    /// Canonical form of the world seed, which `from_seed` maps back to this world
+    pub fn seed(&self) -> String {
+        self.seed.to_string()
+    }
+    
+    /// 64-bit world seed
+    pub fn seed_value(&self) -> u64 {
+        self.seed.value()
+    }
+    
+    /// Current configuration as JSON
+    pub fn config(&self) -> String {
+        self.chunk_cache.config().to_json()
+    }
+    
+    /// Generator version this world was created with
+    pub fn generator_version(&self) -> u32 {
+        self.chunk_cache.config().generator_version
+    }
+    
+    /// Stable identifier of the world ruleset (16 hex digits)
+    pub fn ruleset_id(&self) -> String {
+        self.chunk_cache.config().ruleset_id()
+    }
+    
+    /// Select city placement: "uniform" or "blue_noise" (with `min_distance` in cells).
+    /// Version 1 worlds only place uniformly; blue noise needs a config with
+    /// `generator_version` 2 or later.
+    pub fn set_placement(&mut self, name: &str, min_distance: f64) -> Result<(), JsValue> {
+        let strategy = PlacementStrategy::from_name(name, min_distance)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.chunk_cache
+            .set_strategy(strategy)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 
     /// Get valid city coordinates in a chunk
-    pub fn get_cities_in_chunk(&self, chunk_x: i32, chunk_y: i32) -> Vec<f64> {
-        // We create a temporary cache to generate just this chunk deterministically
-        // Note: In a real server scenario, we might want to keep the cache or be stateless
-        // Since ChunkCache::new(seed) is cheap, we can just use the internal one
-        // But `generate_chunk` is private in ChunkCache, so we need to use get_or_generate
-        
-        let mut cache = ChunkCache::new(self.seed);
+    pub fn get_cities_in_chunk(&mut self, chunk_x: i32, chunk_y: i32) -> Vec<f64> {
         let coord = ChunkCoord::new(chunk_x, chunk_y);
-        let data = cache.get_or_generate(coord);
+        let data = self.chunk_cache.get_or_generate(coord);
         
         let mut result = Vec::with_capacity(data.cities.len() * 3);
         for city in &data.cities {
-            result.push(city.world_x(&coord));
-            result.push(city.world_y(&coord));
+            let (x, y) = city.world_position(&coord, data.size);
+            result.push(x);
+            result.push(y);
             result.push(city.seed as f64);
         }
         result
     }
     
-    /// Verify if a city exists at a rough world location (tolerance check)
-    pub fn verify_city(&self, world_x: f64, world_y: f64) -> bool {
-        let chunk_x = (world_x / CHUNK_SIZE as f64).floor() as i32;
-        let chunk_y = (world_y / CHUNK_SIZE as f64).floor() as i32;
+    /// Cities inside a world rectangle, bounds inclusive, in row-major order.
+    /// Format: [world_x, world_y, seed, ...]
+    pub fn get_cities_in_rect(
+        &mut self,
+        min_x: f64,
+        min_y: f64,
+        max_x: f64,
+        max_y: f64,
+    ) -> Result<Vec<f64>, JsValue> {
+        let cities = cities_in_rect(&mut self.chunk_cache, min_x, min_y, max_x, max_y)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(flatten_cities(&cities))
+    }
+    
+    /// Cities within `radius` cells of a world point, nearest first.
+    /// Format: [world_x, world_y, seed, ...]
+    pub fn get_cities_in_radius(&mut self, x: f64, y: f64, radius: f64) -> Result<Vec<f64>, JsValue> {
+        let cities = cities_in_radius(&mut self.chunk_cache, x, y, radius)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(flatten_cities(&cities))
+    }
+    
+    /// The `count` cities nearest a world point, nearest first.
+    /// Format: [world_x, world_y, seed, ...]
+    pub fn get_nearest_cities(&mut self, x: f64, y: f64, count: u32) -> Result<Vec<f64>, JsValue> {
+        let cities = nearest_cities(&mut self.chunk_cache, x, y, count as usize)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(flatten_cities(&cities))
+    }
+    
+    /// Roads with at least one endpoint in a chunk, reproducible from the seed.
+    /// Format: [from_x, from_y, to_x, to_y, ...] in world cell coordinates
+    pub fn get_roads_in_chunk(&mut self, chunk_x: i32, chunk_y: i32) -> Vec<f64> {
+        let roads = roads_touching_chunk(&mut self.chunk_cache, ChunkCoord::new(chunk_x, chunk_y));
+        
+        let mut result = Vec::with_capacity(roads.len() * 4);
+        for road in &roads {
+            result.push(road.from.0 as f64);
+            result.push(road.from.1 as f64);
+            result.push(road.to.0 as f64);
+            result.push(road.to.1 as f64);
+        }
+        result
+    }
+    
+    /// Territory ownership of a chunk's cells.
+    /// Format: [num_owners, (city_x, city_y, city_seed, nation) * num_owners,
+    /// then chunk_size x chunk_size row-major owner indices, -1 for unclaimed]
+    pub fn get_territory_in_chunk(&mut self, chunk_x: i32, chunk_y: i32) -> Vec<f64> {
+        let territories = territories_for_chunk(
+            &mut self.chunk_cache,
+            ChunkCoord::new(chunk_x, chunk_y),
+            self.seed.value(),
+        );
+        
+        let mut result = Vec::with_capacity(1 + territories.owners.len() * 4 + territories.cells().len());
+        result.push(territories.owners.len() as f64);
+        for owner in &territories.owners {
+            result.push(owner.city.0 as f64);
+            result.push(owner.city.1 as f64);
+            result.push(owner.city_seed as f64);
+            result.push(owner.nation as f64);
+        }
+        for cell in territories.cells() {
+            result.push(cell.map_or(-1.0, |i| i as f64));
+        }
+        result
+    }
+    
+    /// Territory and national borders on a chunk's cell edges.
+    /// Format: [from_x, from_y, to_x, to_y, national, ...] in world lattice coordinates
+    pub fn get_borders_in_chunk(&mut self, chunk_x: i32, chunk_y: i32) -> Vec<f64> {
+        let territories = territories_for_chunk(
+            &mut self.chunk_cache,
+            ChunkCoord::new(chunk_x, chunk_y),
+            self.seed.value(),
+        );
+        
+        let mut result = Vec::with_capacity(territories.borders.len() * 5);
+        for border in &territories.borders {
+            result.push(border.from.0 as f64);
+            result.push(border.from.1 as f64);
+            result.push(border.to.0 as f64);
+            result.push(border.to.1 as f64);
+            result.push(if border.national { 1.0 } else { 0.0 });
+        }
+        result
+    }
+    
+    /// Macro structure of a super-chunk region (REGION_SIZE x REGION_SIZE chunks).
+    /// Format: [seed, has_capital, capital_x, capital_y, num_hubs, (x, y) * num_hubs,
+    /// num_zones, (centre_x, centre_y, radius) * num_zones] in world cells
+    pub fn get_region(&self, region_x: i32, region_y: i32) -> Vec<f64> {
+        let region = self.chunk_cache.region_data(RegionCoord::new(region_x, region_y));
+        
+        let (has_capital, (capital_x, capital_y)) = match region.capital {
+            Some(c) => (1.0, c),
+            None => (0.0, (0, 0)),
+        };
+        let mut result = vec![region.seed as f64, has_capital, capital_x as f64, capital_y as f64];
+        result.push(region.trade_hubs.len() as f64);
+        for (x, y) in &region.trade_hubs {
+            result.push(*x as f64);
+            result.push(*y as f64);
+        }
+        result.push(region.wilderness.len() as f64);
+        for zone in &region.wilderness {
+            result.push(zone.centre.0 as f64);
+            result.push(zone.centre.1 as f64);
+            result.push(zone.radius as f64);
+        }
+        result
+    }
+    
+    /// Biome ids for every cell of a chunk, row-major chunk_size x chunk_size
+    /// (0 ocean, 1 plains, 2 forest, 3 mountains, 4 desert, 5 lake)
+    pub fn get_terrain_in_chunk(&mut self, chunk_x: i32, chunk_y: i32) -> Vec<u8> {
+        let data = self.chunk_cache.get_or_generate(ChunkCoord::new(chunk_x, chunk_y));
+        data.terrain.cells().map(|b| b as u8).collect()
+    }
+    
+    /// Rivers passing through a chunk, each in full from source to mouth.
+    /// Format: [source_chunk_x, source_chunk_y, ends_in_lake, num_points, x1, y1, ...]
+    pub fn get_rivers_in_chunk(&mut self, chunk_x: i32, chunk_y: i32) -> Vec<f64> {
+        let data = self.chunk_cache.get_or_generate(ChunkCoord::new(chunk_x, chunk_y));
         
-        let mut cache = ChunkCache::new(self.seed);
+        let mut result = Vec::new();
+        for river in &data.rivers {
+            result.push(river.source_chunk.x as f64);
+            result.push(river.source_chunk.y as f64);
+            result.push(if river.ends_in_lake { 1.0 } else { 0.0 });
+            result.push(river.points.len() as f64);
+            for (x, y) in &river.points {
+                result.push(*x as f64);
+                result.push(*y as f64);
+            }
+        }
+        result
+    }
+    
+    /// Points of interest in a chunk.
+    /// Format: [kind, world_x, world_y, seed, kind, world_x, world_y, seed, ...]
+    pub fn get_pois_in_chunk(&mut self, chunk_x: i32, chunk_y: i32) -> Vec<f64> {
         let coord = ChunkCoord::new(chunk_x, chunk_y);
-        let data = cache.get_or_generate(coord);
+        let data = self.chunk_cache.get_or_generate(coord);
         
-        for city in &data.cities {
-             let cx = city.world_x(&coord);
-             let cy = city.world_y(&coord);
-             
-             // Exact match check (float tolerance)
-             if (cx - world_x).abs() < 0.01 && (cy - world_y).abs() < 0.01 {
-                 return true;
-             }
+        let (origin_x, origin_y) = coord.world_origin(data.size);
+        let mut result = Vec::with_capacity(data.pois.len() * 4);
+        for poi in &data.pois {
+            result.push(poi.kind as u32 as f64);
+            result.push((origin_x + poi.grid_x as i64) as f64);
+            result.push((origin_y + poi.grid_y as i64) as f64);
+            result.push(poi.seed as f64);
+        }
+        result
+    }
+    
+    /// Derive a POI's name, tier and kind-specific value from its kind and seed
+    pub fn get_poi_properties(&self, kind: PoiKind, poi_seed: u32) -> PoiProperties {
+        PoiProperties::from_seed(kind, poi_seed)
+    }
+    
+    /// Derive a city's name, population, size class and founding era from its seed
+    pub fn get_city_attributes(&self, city_seed: u32) -> CityAttributes {
+        CityAttributes::from_seed(city_seed)
+    }
+    
+    /// Verify if a city exists within 0.01 cells of a world location on each axis
+    pub fn verify_city(&mut self, world_x: f64, world_y: f64) -> bool {
+        // Cities stand on whole cells, so only the nearest cell can match; it may
+        // lie in the chunk next to the one holding the point
+        let (cell_x, cell_y) = (world_x.round(), world_y.round());
+        if !((cell_x - world_x).abs() < 0.01 && (cell_y - world_y).abs() < 0.01) {
+            return false;
+        }
+        match (whole::<i32>(cell_x), whole::<i32>(cell_y)) {
+            (Some(x), Some(y)) => city_at(&mut self.chunk_cache, x, y).is_some(),
+            _ => false,
+        }
+    }
+    
+    /// Full record of the city nearest a world location within `tolerance` cells
+    pub fn lookup_city(
+        &mut self,
+        world_x: f64,
+        world_y: f64,
+        tolerance: f64,
+    ) -> Result<Option<CityRecord>, JsValue> {
+        let city = city_near(&mut self.chunk_cache, world_x, world_y, tolerance)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(city.map(|c| c.record()))
+    }
+    
+    /// Full record of the city on an exact world cell
+    pub fn lookup_city_at(&mut self, world_x: i32, world_y: i32) -> Option<CityRecord> {
+        city_at(&mut self.chunk_cache, world_x, world_y).map(|c| c.record())
+    }
+    
+    /// Verify many claimed cities at once, e.g. every stop of a tour.
+    /// Claims: [world_x, world_y, seed, ...]; a claim holds when a city with that
+    /// seed stands on that exact cell. Result: 1 or 0 per claim.
+    pub fn verify_cities(&mut self, claims: Vec<f64>) -> Result<Vec<u8>, JsValue> {
+        if !claims.len().is_multiple_of(3) {
+            return Err(JsValue::from_str("claims must be [world_x, world_y, seed] triples"));
         }
-        false
+        let parsed: Vec<Option<(i32, i32, u32)>> = claims
+            .chunks_exact(3)
+            .map(|claim| Some((whole(claim[0])?, whole(claim[1])?, whole(claim[2])?)))
+            .collect();
+        let valid: Vec<(i32, i32, u32)> = parsed.iter().flatten().copied().collect();
+        // Claims that are not whole in-range values never hold
+        let mut verified = query::verify_cities(&mut self.chunk_cache, &valid).into_iter();
+        let result = parsed
+            .iter()
+            .map(|claim| claim.is_some() && verified.next() == Some(true))
+            .map(u8::from)
+            .collect();
+        Ok(result)
+    }
+}
+
+impl WorldGenerator {
+    fn build(seed: WorldSeed, config_json: &str) -> Result<WorldGenerator, JsValue> {
+        let config = WorldConfig::from_json(config_json)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let chunk_cache = ChunkCache::with_config(seed.value(), config)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(WorldGenerator { seed, chunk_cache })
+    }
+}
+
+/// Cities as [world_x, world_y, seed, ...]
+fn flatten_cities(cities: &[WorldCity]) -> Vec<f64> {
+    let mut result = Vec::with_capacity(cities.len() * 3);
+    for city in cities {
+        result.push(city.x as f64);
+        result.push(city.y as f64);
+        result.push(city.seed as f64);
+    }
+    result
+}
+
+/// A float holding a whole number representable as `T`
+fn whole<T: TryFrom<i64>>(value: f64) -> Option<T> {
+    if value.fract() != 0.0 || !(i64::MIN as f64..i64::MAX as f64).contains(&value) {
+        return None;
     }
+    T::try_from(value as i64).ok()
 }
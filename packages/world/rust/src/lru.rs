@@ -0,0 +1,142 @@
+//! Least-recently-used map
+//!
+//! A hash map into a dense slab of entries threaded on a doubly linked recency
+//! list. Lookup, touch, insert and eviction are all O(1); removal moves the
+//! last slab entry into the freed slot so the slab never has holes.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// End of the recency list
+const NIL: usize = usize::MAX;
+
+#[derive(Clone)]
+struct Entry<K, V> {
+    key: K,
+    value: V,
+    /// Next more recently used entry
+    newer: usize,
+    /// Next less recently used entry
+    older: usize,
+}
+
+#[derive(Clone)]
+pub(crate) struct Lru<K, V> {
+    index: HashMap<K, usize>,
+    entries: Vec<Entry<K, V>>,
+    newest: usize,
+    oldest: usize,
+}
+
+impl<K: Hash + Eq + Copy, V> Lru<K, V> {
+    pub fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            entries: Vec::new(),
+            newest: NIL,
+            oldest: NIL,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Look up without changing recency
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.index.get(key).map(|&slot| &self.entries[slot].value)
+    }
+
+    /// Mark as most recently used and return the value
+    pub fn touch(&mut self, key: &K) -> Option<&mut V> {
+        let slot = *self.index.get(key)?;
+        self.unlink(slot);
+        self.link_newest(slot);
+        Some(&mut self.entries[slot].value)
+    }
+
+    /// Insert as most recently used, replacing any previous value
+    pub fn insert(&mut self, key: K, value: V) {
+        if let Some(&slot) = self.index.get(&key) {
+            self.entries[slot].value = value;
+            self.unlink(slot);
+            self.link_newest(slot);
+            return;
+        }
+        let slot = self.entries.len();
+        self.entries.push(Entry {
+            key,
+            value,
+            newer: NIL,
+            older: NIL,
+        });
+        self.index.insert(key, slot);
+        self.link_newest(slot);
+    }
+
+    /// Remove and return the least recently used entry
+    pub fn pop_oldest(&mut self) -> Option<(K, V)> {
+        if self.oldest == NIL {
+            return None;
+        }
+        let slot = self.oldest;
+        self.unlink(slot);
+        let entry = self.entries.swap_remove(slot);
+        self.index.remove(&entry.key);
+
+        // Repoint the entry moved into `slot`
+        if slot < self.entries.len() {
+            let moved = &self.entries[slot];
+            let (key, newer, older) = (moved.key, moved.newer, moved.older);
+            self.index.insert(key, slot);
+            match newer {
+                NIL => self.newest = slot,
+                n => self.entries[n].older = slot,
+            }
+            match older {
+                NIL => self.oldest = slot,
+                o => self.entries[o].newer = slot,
+            }
+        }
+        Some((entry.key, entry.value))
+    }
+
+    /// Remove everything, returning the keys
+    pub fn clear(&mut self) -> impl Iterator<Item = K> + '_ {
+        self.index.clear();
+        self.newest = NIL;
+        self.oldest = NIL;
+        self.entries.drain(..).map(|e| e.key)
+    }
+
+    /// Values, in no particular order
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|e| &e.value)
+    }
+
+    fn unlink(&mut self, slot: usize) {
+        let (newer, older) = (self.entries[slot].newer, self.entries[slot].older);
+        match newer {
+            NIL => self.newest = older,
+            n => self.entries[n].older = older,
+        }
+        match older {
+            NIL => self.oldest = newer,
+            o => self.entries[o].newer = newer,
+        }
+    }
+
+    fn link_newest(&mut self, slot: usize) {
+        self.entries[slot].newer = NIL;
+        self.entries[slot].older = self.newest;
+        match self.newest {
+            NIL => self.oldest = slot,
+            n => self.entries[n].newer = slot,
+        }
+        self.newest = slot;
+    }
+}
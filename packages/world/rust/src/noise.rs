@@ -0,0 +1,49 @@
+//! Coherent noise
+//!
+//! Value noise over the integer lattice hash. Plain f64 arithmetic (no fused ops)
+//! keeps results identical on every IEEE-754 platform.
+
+use crate::hash::{hash2, mix64, unit_f64};
+
+/// Smoothstep-interpolated value noise on the integer lattice, in [0.0, 1.0)
+pub fn value_noise(seed: u64, x: f64, y: f64) -> f64 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let tx = x - x0;
+    let ty = y - y0;
+    let sx = tx * tx * (3.0 - 2.0 * tx);
+    let sy = ty * ty * (3.0 - 2.0 * ty);
+
+    let v00 = unit_f64(hash2(seed, ix, iy));
+    let v10 = unit_f64(hash2(seed, ix.wrapping_add(1), iy));
+    let v01 = unit_f64(hash2(seed, ix, iy.wrapping_add(1)));
+    let v11 = unit_f64(hash2(seed, ix.wrapping_add(1), iy.wrapping_add(1)));
+
+    let top = v00 + (v10 - v00) * sx;
+    let bottom = v01 + (v11 - v01) * sx;
+    top + (bottom - top) * sy
+}
+
+/// Fractal sum of value noise octaves, normalized to [0.0, 1.0).
+/// `wavelength` is the size of the lowest octave's features, in cells.
+pub fn fbm(seed: u64, x: f64, y: f64, wavelength: f64, octaves: u32) -> f64 {
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut norm = 0.0;
+    let mut frequency = 1.0 / wavelength;
+    for octave in 0..octaves {
+        let octave_seed = mix64(seed.wrapping_add(octave as u64));
+        total += value_noise(octave_seed, x * frequency, y * frequency) * amplitude;
+        norm += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    total / norm
+}
+
+/// Hermite smoothstep of `x` between `edge0` and `edge1`
+pub fn smoothstep(edge0: f64, edge1: f64, x: f64) -> f64 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
@@ -0,0 +1,165 @@
+//! Points of interest beyond cities
+//!
+//! Depots, markets, landmarks and hazards are placed per chunk from position
+//! hashes rather than the city RNG stream, so adding them never moves a city.
+//! Like cities, each carries a 30-bit seed that expands into its properties.
+
+use wasm_bindgen::prelude::*;
+
+use crate::attributes::city_name;
+use crate::chunk::{ChunkCoord, City};
+use crate::hash::{hash2, mix32, unit_f64};
+use crate::hydrology::River;
+use crate::seed::field_seed;
+use crate::terrain::TerrainGrid;
+use std::collections::HashSet;
+
+const SALT_POI: u32 = 0x504F_4921;
+
+/// Placement attempts per chunk for each kind
+const ATTEMPTS: u32 = 4;
+
+/// Landmark name suffixes
+const LANDMARK_SUFFIXES: [&str; 8] = [
+    "Spire", "Falls", "Arch", "Monolith", "Ruins", "Obelisk", "Crater", "Grove",
+];
+
+/// Hazard names
+const HAZARD_NAMES: [&str; 6] = [
+    "Sinkhole",
+    "Bandit Camp",
+    "Toxic Marsh",
+    "Rockslide",
+    "Quicksand",
+    "Storm Front",
+];
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PoiKind {
+    Depot = 0,
+    Market = 1,
+    Landmark = 2,
+    Hazard = 3,
+}
+
+impl PoiKind {
+    pub const ALL: [PoiKind; 4] = [
+        PoiKind::Depot,
+        PoiKind::Market,
+        PoiKind::Landmark,
+        PoiKind::Hazard,
+    ];
+
+    /// Chance that each placement attempt yields a POI of this kind
+    fn frequency(&self) -> f64 {
+        match self {
+            PoiKind::Depot => 0.5,
+            PoiKind::Market => 0.25,
+            PoiKind::Landmark => 0.1,
+            PoiKind::Hazard => 0.25,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Option<PoiKind> {
+        Self::ALL.get(id as usize).copied()
+    }
+}
+
+/// A point of interest inside a chunk
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PointOfInterest {
+    pub kind: PoiKind,
+    pub grid_x: i32,
+    pub grid_y: i32,
+    pub seed: u32,
+}
+
+impl PointOfInterest {
+    pub fn properties(&self) -> PoiProperties {
+        PoiProperties::from_seed(self.kind, self.seed)
+    }
+}
+
+/// Seed-derived properties of a POI
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoiProperties {
+    pub kind: PoiKind,
+    pub name: String,
+    /// Importance from 1 (minor) to 5 (major)
+    pub tier: u32,
+    /// Depot: storage capacity. Market: price index in percent.
+    /// Landmark: prestige points. Hazard: radius of effect in cells.
+    pub value: u32,
+}
+
+impl PoiProperties {
+    pub fn from_seed(kind: PoiKind, seed: u32) -> Self {
+        let roll = |index: u32| mix32(seed ^ mix32(SALT_POI.wrapping_add(index)));
+        let tier = 1 + roll(0) % 5;
+        let base = city_name(seed);
+        let (name, value) = match kind {
+            PoiKind::Depot => (format!("{} Depot", base), tier * 200 + roll(1) % 200),
+            PoiKind::Market => (format!("{} Market", base), 80 + roll(1) % 41),
+            PoiKind::Landmark => {
+                let suffix = LANDMARK_SUFFIXES[(roll(1) % LANDMARK_SUFFIXES.len() as u32) as usize];
+                (format!("{} {}", base, suffix), tier * 10 + roll(2) % 10)
+            }
+            PoiKind::Hazard => {
+                let hazard = HAZARD_NAMES[(roll(1) % HAZARD_NAMES.len() as u32) as usize];
+                (format!("{} {}", base, hazard), 1 + tier / 2 + roll(2) % 2)
+            }
+        };
+        Self {
+            kind,
+            name,
+            tier,
+            value,
+        }
+    }
+}
+
+/// Place POIs in a chunk on free land cells (never on a city, river, water or another POI)
+pub fn place_pois(
+    world_seed: u64,
+    coord: ChunkCoord,
+    terrain: &TerrainGrid,
+    cities: &[City],
+    rivers: &[River],
+) -> Vec<PointOfInterest> {
+    let chunk_hash = hash2(field_seed(world_seed, SALT_POI), coord.x, coord.y);
+    let size = terrain.size();
+    let (origin_x, origin_y) = coord.origin(size);
+    let mut occupied: HashSet<(i32, i32)> = cities
+        .iter()
+        .map(|c| (c.grid_x, c.grid_y))
+        .chain(rivers.iter().flat_map(|r| &r.points).map(|&(x, y)| {
+            (x.wrapping_sub(origin_x), y.wrapping_sub(origin_y))
+        }))
+        .collect();
+
+    let mut pois = Vec::new();
+    for kind in PoiKind::ALL {
+        for attempt in 0..ATTEMPTS {
+            let h = mix32(chunk_hash ^ mix32((kind as u32) << 8 | attempt));
+            if unit_f64(h) >= kind.frequency() {
+                continue;
+            }
+            let cell = (
+                (mix32(h ^ 1) % size as u32) as i32,
+                (mix32(h ^ 2) % size as u32) as i32,
+            );
+            if terrain.get(cell.0, cell.1).is_water() || !occupied.insert(cell) {
+                continue;
+            }
+            pois.push(PointOfInterest {
+                kind,
+                grid_x: cell.0,
+                grid_y: cell.1,
+                seed: mix32(h ^ 3) & 0x3FFF_FFFF,
+            });
+        }
+    }
+    pois
+}
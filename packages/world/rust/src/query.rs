@@ -0,0 +1,296 @@
+//! City queries across chunks
+//!
+//! Rectangle, radius and nearest-neighbour lookups in world cell coordinates,
+//! and verification of claimed cities. Each query generates the chunks it
+//! overlaps through a `ChunkCache`, up to `MAX_QUERY_CHUNKS` chunks, and
+//! returns cities in a deterministic order.
+
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+use crate::attributes::CityAttributes;
+use crate::chunk::{ChunkCache, ChunkCoord};
+use crate::generator::ChunkGenerator;
+
+/// Most chunks a single query may generate
+pub const MAX_QUERY_CHUNKS: u64 = 1024;
+
+/// Why a query was rejected
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryError {
+    /// Non-finite or out-of-range coordinates, inverted bounds or a negative radius
+    InvalidArea,
+    /// The area overlaps more than `MAX_QUERY_CHUNKS` chunks (`u64::MAX` when
+    /// the count itself overflows)
+    TooManyChunks(u64),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::InvalidArea => write!(f, "invalid query area"),
+            QueryError::TooManyChunks(chunks) => write!(
+                f,
+                "query spans {} chunks, more than {}",
+                chunks, MAX_QUERY_CHUNKS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// A city located in the world
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WorldCity {
+    pub chunk: ChunkCoord,
+    /// Cell within the chunk
+    pub grid_x: i32,
+    pub grid_y: i32,
+    /// World cell
+    pub x: i32,
+    pub y: i32,
+    pub seed: u32,
+}
+
+impl WorldCity {
+    /// Euclidean distance from a world point
+    pub fn distance_to(&self, x: f64, y: f64) -> f64 {
+        (self.x as f64 - x).hypot(self.y as f64 - y)
+    }
+
+    /// Derive name, population, size class and founding era from the seed
+    pub fn attributes(&self) -> CityAttributes {
+        CityAttributes::from_seed(self.seed)
+    }
+
+    /// Full record, attributes included
+    pub fn record(&self) -> CityRecord {
+        CityRecord {
+            chunk_x: self.chunk.x,
+            chunk_y: self.chunk.y,
+            grid_x: self.grid_x,
+            grid_y: self.grid_y,
+            world_x: self.x,
+            world_y: self.y,
+            seed: self.seed,
+            attributes: self.attributes(),
+        }
+    }
+}
+
+/// Everything known about a city: where it is and what its seed expands into
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CityRecord {
+    pub chunk_x: i32,
+    pub chunk_y: i32,
+    /// Cell within the chunk
+    pub grid_x: i32,
+    pub grid_y: i32,
+    /// World cell
+    pub world_x: i32,
+    pub world_y: i32,
+    pub seed: u32,
+    pub attributes: CityAttributes,
+}
+
+/// Chunk range covering world cells `min..=max` along one axis
+fn chunk_span(min: f64, max: f64, chunk_size: i32) -> Result<(i32, i32), QueryError> {
+    let range = i32::MIN as f64..=i32::MAX as f64;
+    if !(range.contains(&min) && range.contains(&max) && min <= max) {
+        return Err(QueryError::InvalidArea);
+    }
+    let size = chunk_size as f64;
+    Ok(((min / size).floor() as i32, (max / size).floor() as i32))
+}
+
+/// Every city of the chunks in an inclusive chunk rectangle
+fn cities_in_chunks<G: ChunkGenerator>(
+    cache: &mut ChunkCache<G>,
+    (min_cx, max_cx): (i32, i32),
+    (min_cy, max_cy): (i32, i32),
+) -> Result<Vec<WorldCity>, QueryError> {
+    // Each span is at most 2^32, so the product can overflow u64;
+    // saturating at u64::MAX still reports too many chunks
+    let chunks = ((max_cx as i64 - min_cx as i64 + 1) as u64)
+        .saturating_mul((max_cy as i64 - min_cy as i64 + 1) as u64);
+    if chunks > MAX_QUERY_CHUNKS {
+        return Err(QueryError::TooManyChunks(chunks));
+    }
+    let mut cities = Vec::new();
+    for cy in min_cy..=max_cy {
+        for cx in min_cx..=max_cx {
+            let coord = ChunkCoord::new(cx, cy);
+            let data = cache.get_or_generate(coord);
+            let (origin_x, origin_y) = coord.origin(data.size);
+            cities.extend(data.cities.iter().map(|c| WorldCity {
+                chunk: coord,
+                grid_x: c.grid_x,
+                grid_y: c.grid_y,
+                x: origin_x.wrapping_add(c.grid_x),
+                y: origin_y.wrapping_add(c.grid_y),
+                seed: c.seed,
+            }));
+        }
+    }
+    Ok(cities)
+}
+
+/// Sort nearest first; position breaks distance ties
+fn sort_by_distance(cities: &mut [WorldCity], x: f64, y: f64) {
+    cities.sort_by(|a, b| {
+        a.distance_to(x, y)
+            .total_cmp(&b.distance_to(x, y))
+            .then((a.y, a.x).cmp(&(b.y, b.x)))
+    });
+}
+
+/// Cities inside a world rectangle, bounds inclusive, in row-major order
+pub fn cities_in_rect<G: ChunkGenerator>(
+    cache: &mut ChunkCache<G>,
+    min_x: f64,
+    min_y: f64,
+    max_x: f64,
+    max_y: f64,
+) -> Result<Vec<WorldCity>, QueryError> {
+    let size = cache.chunk_size();
+    let span_x = chunk_span(min_x, max_x, size)?;
+    let span_y = chunk_span(min_y, max_y, size)?;
+    let mut cities = cities_in_chunks(cache, span_x, span_y)?;
+    cities.retain(|c| {
+        (min_x..=max_x).contains(&(c.x as f64)) && (min_y..=max_y).contains(&(c.y as f64))
+    });
+    cities.sort_by_key(|c| (c.y, c.x));
+    Ok(cities)
+}
+
+/// Cities within `radius` cells of a world point (inclusive), nearest first
+pub fn cities_in_radius<G: ChunkGenerator>(
+    cache: &mut ChunkCache<G>,
+    x: f64,
+    y: f64,
+    radius: f64,
+) -> Result<Vec<WorldCity>, QueryError> {
+    if radius.is_nan() || radius < 0.0 {
+        return Err(QueryError::InvalidArea);
+    }
+    let size = cache.chunk_size();
+    let span_x = chunk_span(x - radius, x + radius, size)?;
+    let span_y = chunk_span(y - radius, y + radius, size)?;
+    let mut cities = cities_in_chunks(cache, span_x, span_y)?;
+    cities.retain(|c| c.distance_to(x, y) <= radius);
+    sort_by_distance(&mut cities, x, y);
+    Ok(cities)
+}
+
+/// The `k` cities nearest a world point, nearest first. Searches outwards in
+/// rings of chunks and stops once no unsearched chunk can hold a nearer city,
+/// or when the search reaches `MAX_QUERY_CHUNKS` chunks; fewer than `k` cities
+/// are returned only in that case.
+pub fn nearest_cities<G: ChunkGenerator>(
+    cache: &mut ChunkCache<G>,
+    x: f64,
+    y: f64,
+    k: usize,
+) -> Result<Vec<WorldCity>, QueryError> {
+    let size = cache.chunk_size();
+    let (cx, _) = chunk_span(x, x, size)?;
+    let (cy, _) = chunk_span(y, y, size)?;
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut found = Vec::new();
+    let mut ring = 0i64;
+    while ((2 * ring + 1) as u64).pow(2) <= MAX_QUERY_CHUNKS {
+        // Chunks on the square ring at Chebyshev distance `ring`
+        let (min_cx, max_cx) = (cx as i64 - ring, cx as i64 + ring);
+        let (min_cy, max_cy) = (cy as i64 - ring, cy as i64 + ring);
+        let chunk_range = i32::MIN as i64..=i32::MAX as i64;
+        if ![min_cx, max_cx, min_cy, max_cy]
+            .iter()
+            .all(|c| chunk_range.contains(c))
+        {
+            break;
+        }
+        for ry in min_cy..=max_cy {
+            let edge_row = ry == min_cy || ry == max_cy;
+            let step = if edge_row {
+                1
+            } else {
+                (max_cx - min_cx).max(1)
+            };
+            let mut rx = min_cx;
+            while rx <= max_cx {
+                let (span_x, span_y) = ((rx as i32, rx as i32), (ry as i32, ry as i32));
+                found.extend(cities_in_chunks(cache, span_x, span_y)?);
+                rx += step;
+            }
+        }
+
+        // Nothing outside the searched square is nearer than its nearest edge
+        if found.len() >= k {
+            let reach = [
+                x - (min_cx * size as i64) as f64,
+                ((max_cx + 1) * size as i64) as f64 - x,
+                y - (min_cy * size as i64) as f64,
+                ((max_cy + 1) * size as i64) as f64 - y,
+            ]
+            .into_iter()
+            .fold(f64::INFINITY, f64::min);
+            sort_by_distance(&mut found, x, y);
+            if found[k - 1].distance_to(x, y) <= reach {
+                break;
+            }
+        }
+        ring += 1;
+    }
+
+    sort_by_distance(&mut found, x, y);
+    found.truncate(k);
+    Ok(found)
+}
+
+/// The city on an exact world cell, if any
+pub fn city_at<G: ChunkGenerator>(cache: &mut ChunkCache<G>, x: i32, y: i32) -> Option<WorldCity> {
+    let size = cache.chunk_size();
+    let coord = ChunkCoord::containing(x, y, size);
+    let (origin_x, origin_y) = coord.origin(size);
+    let (grid_x, grid_y) = (x.wrapping_sub(origin_x), y.wrapping_sub(origin_y));
+    let data = cache.get_or_generate(coord);
+    data.cities
+        .iter()
+        .find(|c| (c.grid_x, c.grid_y) == (grid_x, grid_y))
+        .map(|c| WorldCity {
+            chunk: coord,
+            grid_x,
+            grid_y,
+            x,
+            y,
+            seed: c.seed,
+        })
+}
+
+/// The city nearest a world point within `tolerance` cells (inclusive), if any.
+/// Looks across chunk borders, unlike a lookup in the chunk holding the point.
+pub fn city_near<G: ChunkGenerator>(
+    cache: &mut ChunkCache<G>,
+    x: f64,
+    y: f64,
+    tolerance: f64,
+) -> Result<Option<WorldCity>, QueryError> {
+    Ok(cities_in_radius(cache, x, y, tolerance)?.into_iter().next())
+}
+
+/// Check claimed cities, each a world cell and a seed, in one pass.
+/// A claim holds when a city with exactly that seed stands on exactly that cell.
+pub fn verify_cities<G: ChunkGenerator>(
+    cache: &mut ChunkCache<G>,
+    claims: &[(i32, i32, u32)],
+) -> Vec<bool> {
+    claims
+        .iter()
+        .map(|&(x, y, seed)| city_at(cache, x, y).is_some_and(|c| c.seed == seed))
+        .collect()
+}
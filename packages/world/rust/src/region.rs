@@ -0,0 +1,198 @@
+//! Super-chunk regions
+//!
+//! A region is a REGION_SIZE x REGION_SIZE block of chunks with its own seed.
+//! Regions carry the large-scale structure that single chunks cannot: a capital,
+//! a few trade hubs and wilderness zones. Every feature, including its area of
+//! influence, lies inside its own region, so a chunk only consults the region
+//! that encloses it, plus the neighbouring regions' hubs when keeping blue-noise
+//! cities clear of them.
+
+use crate::chunk::ChunkCoord;
+use crate::hash::{hash2, mix32, unit_f64};
+use crate::hydrology::Hydrology;
+use crate::seed::field_seed;
+
+const SALT_REGION: u32 = 0x5245_4749;
+
+/// Chunks per region side
+pub const REGION_SIZE: i32 = 16;
+
+/// Cities within this many cells of a capital or trade hub are never thinned out
+pub const HUB_RADIUS: i32 = 24;
+
+const MIN_TRADE_HUBS: u32 = 2;
+const MAX_TRADE_HUBS: u32 = 4;
+const MAX_WILDERNESS_ZONES: u32 = 3;
+const MIN_WILDERNESS_RADIUS: i32 = 48;
+const MAX_WILDERNESS_RADIUS: i32 = 160;
+
+/// Attempts at finding a land cell for a capital or hub
+const LAND_ATTEMPTS: u32 = 16;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct RegionCoord {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl RegionCoord {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Region enclosing a chunk
+    pub fn from_chunk(chunk: ChunkCoord) -> Self {
+        Self::new(chunk.x.div_euclid(REGION_SIZE), chunk.y.div_euclid(REGION_SIZE))
+    }
+
+    /// World cell of the region's top-left corner
+    pub fn origin(&self, chunk_size: i32) -> (i32, i32) {
+        let cells = REGION_SIZE * chunk_size;
+        (self.x.wrapping_mul(cells), self.y.wrapping_mul(cells))
+    }
+}
+
+/// Circular area with no cities
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WildernessZone {
+    pub centre: (i32, i32),
+    pub radius: i32,
+}
+
+impl WildernessZone {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        let dx = x as i64 - self.centre.0 as i64;
+        let dy = y as i64 - self.centre.1 as i64;
+        dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy)) <= (self.radius as i64).pow(2)
+    }
+}
+
+/// Macro structure of one region, positions in world cells
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionData {
+    pub coord: RegionCoord,
+    pub seed: u32,
+    /// Always a city; None if the region is (nearly) all water
+    pub capital: Option<(i32, i32)>,
+    /// Always cities
+    pub trade_hubs: Vec<(i32, i32)>,
+    pub wilderness: Vec<WildernessZone>,
+}
+
+/// Deterministic seed of a region
+pub fn region_seed(world_seed: u64, coord: RegionCoord) -> u32 {
+    hash2(field_seed(world_seed, SALT_REGION), coord.x, coord.y)
+}
+
+impl RegionData {
+    /// Region without capital, hubs or wilderness, for generators that predate regions
+    pub fn featureless(world_seed: u64, coord: RegionCoord) -> Self {
+        Self {
+            coord,
+            seed: region_seed(world_seed, coord),
+            capital: None,
+            trade_hubs: Vec::new(),
+            wilderness: Vec::new(),
+        }
+    }
+
+    /// Capital and hubs sit on dry land (clear of sea, lakes and rivers), at least
+    /// `spacing` cells from each other and from the hubs of neighbouring regions
+    pub fn generate(
+        world_seed: u64,
+        coord: RegionCoord,
+        hydrology: &Hydrology,
+        chunk_size: i32,
+        spacing: f64,
+    ) -> Self {
+        let seed = region_seed(world_seed, coord);
+        let (ox, oy) = coord.origin(chunk_size);
+        let region_cells = REGION_SIZE * chunk_size;
+        // Independent hash stream per purpose and index
+        let roll = |purpose: u32, index: u32| {
+            mix32(seed ^ mix32(purpose.wrapping_mul(0x9E37_79B9) ^ index))
+        };
+
+        // Land cell at least `margin` from the region edge
+        let find_land = |purpose: u32, margin: i32| {
+            let span = (region_cells - 2 * margin) as u32;
+            (0..LAND_ATTEMPTS)
+                .map(|i| {
+                    (
+                        ox + margin + (roll(purpose, 2 * i) % span) as i32,
+                        oy + margin + (roll(purpose, 2 * i + 1) % span) as i32,
+                    )
+                })
+                .find(|&(x, y)| !hydrology.is_water(x, y))
+        };
+
+        // Capital sits away from the edges, towards the region's heart
+        let capital = find_land(1, region_cells / 4);
+
+        // Half the spacing from the edge keeps hubs of adjacent regions apart too
+        let hub_margin = HUB_RADIUS.max((spacing / 2.0).ceil() as i32);
+        let hub_count = MIN_TRADE_HUBS + roll(2, 0) % (MAX_TRADE_HUBS - MIN_TRADE_HUBS + 1);
+        let mut trade_hubs: Vec<(i32, i32)> = Vec::new();
+        for hub in (0..hub_count).filter_map(|i| find_land(100 + i, hub_margin)) {
+            let crowded = capital.iter().chain(&trade_hubs).any(|&(x, y)| {
+                let (dx, dy) = ((hub.0 - x) as f64, (hub.1 - y) as f64);
+                hub == (x, y) || dx * dx + dy * dy < spacing * spacing
+            });
+            if !crowded {
+                trade_hubs.push(hub);
+            }
+        }
+
+        let zone_count = roll(3, 0) % (MAX_WILDERNESS_ZONES + 1);
+        let wilderness = (0..zone_count)
+            .map(|i| {
+                let spread = (MAX_WILDERNESS_RADIUS - MIN_WILDERNESS_RADIUS) as f64;
+                let radius = MIN_WILDERNESS_RADIUS + (unit_f64(roll(200 + i, 0)) * spread) as i32;
+                let span = (region_cells - 2 * radius) as u32;
+                WildernessZone {
+                    centre: (
+                        ox + radius + (roll(200 + i, 1) % span) as i32,
+                        oy + radius + (roll(200 + i, 2) % span) as i32,
+                    ),
+                    radius,
+                }
+            })
+            // Wilderness never swallows the capital or a hub
+            .filter(|zone| {
+                capital.iter().chain(&trade_hubs).all(|&(x, y)| !zone.contains(x, y))
+            })
+            .collect();
+
+        Self {
+            coord,
+            seed,
+            capital,
+            trade_hubs,
+            wilderness,
+        }
+    }
+
+    /// Capital followed by trade hubs
+    pub fn hubs(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        self.capital.iter().copied().chain(self.trade_hubs.iter().copied())
+    }
+
+    /// Seed of the hub city at `index` in `hubs()` order
+    pub fn hub_city_seed(&self, index: u32) -> u32 {
+        mix32(self.seed ^ 0x4855_4221u32.wrapping_add(index)) & 0x3FFF_FFFF
+    }
+
+    pub fn in_wilderness(&self, x: i32, y: i32) -> bool {
+        self.wilderness.iter().any(|z| z.contains(x, y))
+    }
+
+    /// Whether a cell lies within `HUB_RADIUS` of the capital or a trade hub
+    pub fn near_hub(&self, x: i32, y: i32) -> bool {
+        let r = HUB_RADIUS as i64;
+        self.hubs().any(|(hx, hy)| {
+            let dx = x as i64 - hx as i64;
+            let dy = y as i64 - hy as i64;
+            dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy)) <= r * r
+        })
+    }
+}
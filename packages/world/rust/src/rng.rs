@@ -0,0 +1,80 @@
+//! Portable random number generation
+//!
+//! A specified PCG32 generator and integer sampler, kept in-crate so worlds do
+//! not depend on `rand` implementation details. The algorithms reproduce what
+//! generator versions 1-3 were frozen with (`rand_pcg::Pcg32` 0.3 seeded with
+//! `seed_from_u64`, sampled with `rand` 0.8.5 `gen_range`), and use 64-bit
+//! wrapping arithmetic only, so Lua and Dart ports can follow them line by line.
+
+/// PCG multiplier, shared by the generator and the seed expansion
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+/// Increment of the seed expansion generator
+const EXPANSION_INCREMENT: u64 = 11_634_580_027_462_260_723;
+
+/// XSH RR output function of a 64-bit PCG state
+fn output(state: u64) -> u32 {
+    let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+    xorshifted.rotate_right((state >> 59) as u32)
+}
+
+/// PCG32 (XSH RR 64/32): 64-bit LCG state, 32-bit output
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    /// Generator from a raw state and an odd increment
+    pub fn from_state_increment(state: u64, increment: u64) -> Self {
+        let mut pcg = Self {
+            state: state.wrapping_add(increment),
+            increment,
+        };
+        pcg.step();
+        pcg
+    }
+
+    /// Expand a 64-bit seed: four outputs of an auxiliary PCG, stepped before
+    /// each output, give the low and high words of the state and the increment
+    pub fn from_seed(mut seed: u64) -> Self {
+        let mut words = [0u64; 4];
+        for word in &mut words {
+            seed = seed
+                .wrapping_mul(MULTIPLIER)
+                .wrapping_add(EXPANSION_INCREMENT);
+            *word = output(seed) as u64;
+        }
+        let state = words[0] | words[1] << 32;
+        let increment = (words[2] | words[3] << 32) | 1;
+        Self::from_state_increment(state, increment)
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let state = self.state;
+        self.step();
+        output(state)
+    }
+
+    /// Uniform integer in `low..high` by widening multiply with rejection:
+    /// draw v, split v * range into (hi, lo) 32-bit halves and accept hi when
+    /// lo <= (range << leading_zeros(range)) - 1. Panics if `low >= high`.
+    pub fn range(&mut self, low: i32, high: i32) -> i32 {
+        assert!(low < high, "empty range {}..{}", low, high);
+        let range = high.wrapping_sub(low) as u32;
+        let zone = (range << range.leading_zeros()).wrapping_sub(1);
+        loop {
+            let product = self.next_u32() as u64 * range as u64;
+            if product as u32 <= zone {
+                return low.wrapping_add((product >> 32) as i32);
+            }
+        }
+    }
+}
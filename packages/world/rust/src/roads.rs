@@ -0,0 +1,93 @@
+//! Road network
+//!
+//! Roads are the edges of a bounded relative neighbourhood graph over cities:
+//! two cities are linked when no third city is closer to both of them than they
+//! are to each other, and they are at most one chunk size apart. The length
+//! bound keeps every test local to the 3x3 chunk neighbourhood, so each chunk's
+//! roads can be derived on their own and neighbouring chunks always agree.
+
+use crate::chunk::{ChunkCache, ChunkCoord};
+use crate::generator::ChunkGenerator;
+
+/// Longest possible road, in cells. Must not exceed the chunk size.
+pub fn max_road_length(chunk_size: i32) -> i64 {
+    chunk_size as i64
+}
+
+/// Undirected road between two cities, endpoints in world cell coordinates.
+/// Endpoints are stored in canonical (sorted) order.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Road {
+    pub from: (i32, i32),
+    pub to: (i32, i32),
+}
+
+impl Road {
+    pub fn new(a: (i32, i32), b: (i32, i32)) -> Self {
+        if a <= b {
+            Self { from: a, to: b }
+        } else {
+            Self { from: b, to: a }
+        }
+    }
+
+    pub fn length(&self) -> f64 {
+        (dist_sq(self.from, self.to) as f64).sqrt()
+    }
+}
+
+fn dist_sq(a: (i32, i32), b: (i32, i32)) -> i64 {
+    let dx = a.0 as i64 - b.0 as i64;
+    let dy = a.1 as i64 - b.1 as i64;
+    // Opposite ends of the cell range are farther apart than i64 can square
+    dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy))
+}
+
+/// World cell positions of all cities in a chunk
+fn city_positions<G: ChunkGenerator>(cache: &mut ChunkCache<G>, coord: ChunkCoord) -> Vec<(i32, i32)> {
+    let (origin_x, origin_y) = coord.origin(cache.chunk_size());
+    let data = cache.get_or_generate(coord);
+    data.cities
+        .iter()
+        .map(|c| (origin_x.wrapping_add(c.grid_x), origin_y.wrapping_add(c.grid_y)))
+        .collect()
+}
+
+/// All roads with at least one endpoint in `coord`, sorted.
+/// Generates the 3x3 neighbourhood through `cache` as needed.
+pub fn roads_touching_chunk<G: ChunkGenerator>(cache: &mut ChunkCache<G>, coord: ChunkCoord) -> Vec<Road> {
+    let own = city_positions(cache, coord);
+    let mut nearby = Vec::new();
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let neighbour = ChunkCoord::new(coord.x.wrapping_add(dx), coord.y.wrapping_add(dy));
+            nearby.extend(city_positions(cache, neighbour));
+        }
+    }
+
+    let max_sq = max_road_length(cache.chunk_size()).pow(2);
+    let mut roads = Vec::new();
+    for &p in &own {
+        // Neighbours of p, nearest first
+        let mut candidates: Vec<((i32, i32), i64)> = nearby
+            .iter()
+            .map(|&q| (q, dist_sq(p, q)))
+            .filter(|&(_, d)| d > 0 && d <= max_sq)
+            .collect();
+        candidates.sort_by_key(|&(q, d)| (d, q));
+
+        for (j, &(q, pq)) in candidates.iter().enumerate() {
+            // Any blocker r has d(p, r) < d(p, q), so it sorts before q
+            let blocked = candidates[..j]
+                .iter()
+                .any(|&(r, pr)| pr < pq && dist_sq(q, r) < pq);
+            if !blocked {
+                roads.push(Road::new(p, q));
+            }
+        }
+    }
+
+    roads.sort();
+    roads.dedup();
+    roads
+}
@@ -0,0 +1,137 @@
+//! World seeds
+//!
+//! A world is identified by a 64-bit seed, given either as a number or as a
+//! phrase players can share. Phrases are normalised (trimmed, runs of
+//! whitespace collapsed to one space, ASCII letters lowercased) and hashed with
+//! FNV-1a followed by the SplitMix64 finalizer, so every client derives the
+//! same world from the same words. The canonical form displays a numeric seed
+//! in decimal and a phrase seed as its normalised phrase, and parses back to
+//! the same seed.
+//!
+//! Seeds below 2^32 generate exactly the version 1 worlds the former u32 seeds
+//! did. Layered versions derive every field from all 64 bits.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::hash::mix64;
+
+/// Why a seed was rejected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeedError {
+    /// Nothing left after normalisation
+    Empty,
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeedError::Empty => write!(f, "seed is empty"),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+/// 64-bit seed of one per-world noise or hash field, told apart from the
+/// other fields by its salt
+pub fn field_seed(world_seed: u64, salt: u32) -> u64 {
+    mix64(world_seed ^ mix64(u64::from(salt)))
+}
+
+/// Normalised form of a seed phrase
+pub fn normalize_phrase(phrase: &str) -> String {
+    phrase
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_lowercase()
+}
+
+/// 64-bit seed of a normalised phrase
+fn hash_phrase(phrase: &str) -> u64 {
+    let fnv = phrase.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3)
+    });
+    mix64(fnv)
+}
+
+/// A world seed and, for phrase seeds, the phrase it came from
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WorldSeed {
+    value: u64,
+    phrase: Option<String>,
+}
+
+impl WorldSeed {
+    pub fn from_u64(value: u64) -> Self {
+        Self {
+            value,
+            phrase: None,
+        }
+    }
+
+    /// Seed derived from a phrase; spacing and ASCII case do not matter
+    fn from_phrase(phrase: &str) -> Result<Self, SeedError> {
+        let phrase = normalize_phrase(phrase);
+        if phrase.is_empty() {
+            return Err(SeedError::Empty);
+        }
+        Ok(Self {
+            value: hash_phrase(&phrase),
+            phrase: Some(phrase),
+        })
+    }
+
+    /// Parse user input: a decimal number up to `u64::MAX` is a numeric seed,
+    /// anything else a phrase
+    pub fn parse(text: &str) -> Result<Self, SeedError> {
+        let trimmed = text.trim();
+        if !trimmed.is_empty() && trimmed.bytes().all(|b| b.is_ascii_digit()) {
+            if let Ok(value) = trimmed.parse() {
+                return Ok(Self::from_u64(value));
+            }
+        }
+        Self::from_phrase(text)
+    }
+
+    /// The 64-bit seed generation runs on
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Normalised phrase, for phrase seeds
+    pub fn phrase(&self) -> Option<&str> {
+        self.phrase.as_deref()
+    }
+}
+
+impl From<u32> for WorldSeed {
+    fn from(seed: u32) -> Self {
+        Self::from_u64(seed as u64)
+    }
+}
+
+impl From<u64> for WorldSeed {
+    fn from(seed: u64) -> Self {
+        Self::from_u64(seed)
+    }
+}
+
+impl FromStr for WorldSeed {
+    type Err = SeedError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+/// Canonical form
+impl fmt::Display for WorldSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.phrase {
+            Some(phrase) => f.write_str(phrase),
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
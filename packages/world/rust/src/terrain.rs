@@ -0,0 +1,166 @@
+//! Terrain and biomes
+//!
+//! Coherent value noise evaluated in world cell coordinates, so the field is
+//! continuous across chunk boundaries and any chunk can be generated on its own.
+
+use crate::chunk::ChunkCoord;
+use crate::noise::fbm;
+use crate::seed::field_seed;
+
+/// Salts separating the noise fields derived from one world seed
+const SALT_HEIGHT: u32 = 0x4845_4947;
+const SALT_MOISTURE: u32 = 0x4D4F_4953;
+
+/// Wavelength of the lowest noise octave, in cells
+const WAVELENGTH: f64 = 256.0;
+const OCTAVES: u32 = 4;
+
+/// Height/moisture thresholds for classification
+pub const SEA_LEVEL: f64 = 0.38;
+const MOUNTAIN_LEVEL: f64 = 0.66;
+const DRY_LEVEL: f64 = 0.4;
+const WET_LEVEL: f64 = 0.58;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[repr(u8)]
+pub enum Biome {
+    Ocean = 0,
+    Plains = 1,
+    Forest = 2,
+    Mountains = 3,
+    Desert = 4,
+    /// Set by hydrology, never by the noise classifier
+    Lake = 5,
+}
+
+impl Biome {
+    pub fn is_water(&self) -> bool {
+        matches!(self, Biome::Ocean | Biome::Lake)
+    }
+}
+
+/// Deterministic terrain for a world seed
+#[derive(Clone, Copy, Debug)]
+pub struct TerrainField {
+    height_seed: u64,
+    moisture_seed: u64,
+}
+
+impl TerrainField {
+    pub fn new(world_seed: u64) -> Self {
+        Self {
+            height_seed: field_seed(world_seed, SALT_HEIGHT),
+            moisture_seed: field_seed(world_seed, SALT_MOISTURE),
+        }
+    }
+
+    /// Elevation in [0.0, 1.0) at a world position
+    pub fn height(&self, x: f64, y: f64) -> f64 {
+        fbm(self.height_seed, x, y, WAVELENGTH, OCTAVES)
+    }
+
+    /// Moisture in [0.0, 1.0) at a world position
+    pub fn moisture(&self, x: f64, y: f64) -> f64 {
+        fbm(self.moisture_seed, x, y, WAVELENGTH, OCTAVES)
+    }
+
+    /// Biome of the cell at integer world coordinates
+    pub fn biome_at(&self, world_x: i32, world_y: i32) -> Biome {
+        // Sample cell centres
+        let x = world_x as f64 + 0.5;
+        let y = world_y as f64 + 0.5;
+        classify(self.height(x, y), self.moisture(x, y))
+    }
+
+    /// Biomes for every cell of a chunk
+    pub fn chunk_grid(&self, coord: ChunkCoord, chunk_size: i32) -> TerrainGrid {
+        let (origin_x, origin_y) = coord.origin(chunk_size);
+        let mut cells = Vec::with_capacity((chunk_size * chunk_size) as usize);
+        for gy in 0..chunk_size {
+            for gx in 0..chunk_size {
+                cells.push(self.biome_at(origin_x.wrapping_add(gx), origin_y.wrapping_add(gy)));
+            }
+        }
+        TerrainGrid {
+            size: chunk_size,
+            cells: Cells::Varied(cells),
+        }
+    }
+}
+
+/// Row-major size x size biome grid of one chunk
+#[derive(Clone, Debug)]
+pub struct TerrainGrid {
+    size: i32,
+    cells: Cells,
+}
+
+#[derive(Clone, Debug)]
+enum Cells {
+    /// Every cell is the same biome; nothing is stored per cell
+    Uniform(Biome),
+    Varied(Vec<Biome>),
+}
+
+impl TerrainGrid {
+    /// Biome at local grid coordinates
+    pub fn get(&self, grid_x: i32, grid_y: i32) -> Biome {
+        match &self.cells {
+            Cells::Uniform(biome) => *biome,
+            Cells::Varied(cells) => cells[(grid_y * self.size + grid_x) as usize],
+        }
+    }
+
+    pub(crate) fn set(&mut self, grid_x: i32, grid_y: i32, biome: Biome) {
+        if let Cells::Uniform(fill) = self.cells {
+            self.cells = Cells::Varied(vec![fill; (self.size * self.size) as usize]);
+        }
+        if let Cells::Varied(cells) = &mut self.cells {
+            cells[(grid_y * self.size + grid_x) as usize] = biome;
+        }
+    }
+
+    /// Grid with every cell set to one biome, without storing the cells
+    pub(crate) fn uniform(size: i32, biome: Biome) -> Self {
+        Self {
+            size,
+            cells: Cells::Uniform(biome),
+        }
+    }
+
+    /// Cells per side
+    pub fn size(&self) -> i32 {
+        self.size
+    }
+
+    /// The biome of every cell, for grids built without per-cell terrain
+    /// (version 1 chunks, layered chunks past the i32 cell range and fixtures)
+    pub fn uniform_biome(&self) -> Option<Biome> {
+        match self.cells {
+            Cells::Uniform(biome) => Some(biome),
+            Cells::Varied(_) => None,
+        }
+    }
+
+    /// All cells, row-major
+    pub fn cells(&self) -> impl ExactSizeIterator<Item = Biome> + '_ {
+        (0..(self.size * self.size) as usize).map(move |i| match &self.cells {
+            Cells::Uniform(biome) => *biome,
+            Cells::Varied(cells) => cells[i],
+        })
+    }
+}
+
+fn classify(height: f64, moisture: f64) -> Biome {
+    if height < SEA_LEVEL {
+        Biome::Ocean
+    } else if height > MOUNTAIN_LEVEL {
+        Biome::Mountains
+    } else if moisture < DRY_LEVEL {
+        Biome::Desert
+    } else if moisture > WET_LEVEL {
+        Biome::Forest
+    } else {
+        Biome::Plains
+    }
+}
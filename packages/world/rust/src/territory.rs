@@ -0,0 +1,260 @@
+//! City territories and nations
+//!
+//! Every land cell belongs to its nearest city (a Voronoi partition), as long as
+//! that city is closer than `max_territory_radius`; ocean and far-flung cells stay
+//! unclaimed. The radius bound keeps each chunk's partition computable from its
+//! 3x3 chunk neighbourhood. Cities are grouped into nations by a coarse jittered
+//! lattice of nation centres, so nations span many chunks.
+
+use crate::chunk::{ChunkCache, ChunkCoord};
+use crate::generator::ChunkGenerator;
+use crate::hash::hash2;
+use crate::seed::field_seed;
+use crate::terrain::TerrainGrid;
+
+const SALT_NATION: u32 = 0x4E41_5449;
+
+/// Cells farther than this from every city are unclaimed. Kept below the chunk
+/// size so the one-cell apron around a chunk still only needs the 3x3 neighbourhood.
+pub fn max_territory_radius(chunk_size: i32) -> i64 {
+    chunk_size as i64 - 1
+}
+
+/// Spacing of the nation centre lattice, in cells
+pub const NATION_SPACING: i32 = 256;
+
+/// A city owning territory
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TerritoryOwner {
+    /// City position in world cells
+    pub city: (i32, i32),
+    pub city_seed: u32,
+    /// Nation id, shared by every city of the nation
+    pub nation: u32,
+}
+
+/// Border segment along cell edges between two claimed cells, in world lattice
+/// coordinates. Coasts and frontiers with unclaimed land are not borders.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Border {
+    pub from: (i32, i32),
+    pub to: (i32, i32),
+    /// Separates different nations
+    pub national: bool,
+}
+
+/// Ownership of one chunk's cells
+#[derive(Clone, Debug)]
+pub struct Territories {
+    pub coord: ChunkCoord,
+    /// Cells per chunk side
+    pub size: i32,
+    /// Owners referenced by `cells`
+    pub owners: Vec<TerritoryOwner>,
+    /// Row-major size x size index into `owners`
+    cells: Vec<Option<u16>>,
+    /// Borders on the chunk's cell edges (each shared edge is owned by exactly one chunk)
+    pub borders: Vec<Border>,
+}
+
+impl Territories {
+    /// Owner of the cell at local grid coordinates
+    pub fn owner_at(&self, grid_x: i32, grid_y: i32) -> Option<&TerritoryOwner> {
+        self.cells[(grid_y * self.size + grid_x) as usize].map(|i| &self.owners[i as usize])
+    }
+
+    /// Owner indices, row-major
+    pub fn cells(&self) -> &[Option<u16>] {
+        &self.cells
+    }
+}
+
+/// Nation centre lattice for a world seed
+#[derive(Clone, Copy, Debug)]
+pub struct Nations {
+    seed: u64,
+}
+
+impl Nations {
+    pub fn new(world_seed: u64) -> Self {
+        Self {
+            seed: field_seed(world_seed, SALT_NATION),
+        }
+    }
+
+    /// Jittered centre of a lattice cell, in world cells
+    fn centre(&self, lx: i32, ly: i32) -> (i64, i64) {
+        let h = hash2(self.seed, lx, ly);
+        let jx = (h % NATION_SPACING as u32) as i64;
+        let jy = ((h >> 16) % NATION_SPACING as u32) as i64;
+        (
+            lx as i64 * NATION_SPACING as i64 + jx,
+            ly as i64 * NATION_SPACING as i64 + jy,
+        )
+    }
+
+    /// Nation of a world cell: id of the nearest nation centre
+    pub fn nation_of(&self, world_x: i32, world_y: i32) -> u32 {
+        let lx = world_x.div_euclid(NATION_SPACING);
+        let ly = world_y.div_euclid(NATION_SPACING);
+        let mut best = (i64::MAX, 0u32);
+        // Jitter stays inside its lattice cell, so the 5x5 block holds the nearest centre
+        for dy in -2..=2 {
+            for dx in -2..=2 {
+                let (cx, cy) = (lx.wrapping_add(dx), ly.wrapping_add(dy));
+                let (px, py) = self.centre(cx, cy);
+                let d = (px - world_x as i64).pow(2) + (py - world_y as i64).pow(2);
+                let id = hash2(self.seed, cx, cy);
+                if (d, id) < best {
+                    best = (d, id);
+                }
+            }
+        }
+        best.1
+    }
+}
+
+/// Territory ownership and borders for a chunk.
+/// Generates the 3x3 neighbourhood through `cache` as needed.
+pub fn territories_for_chunk<G: ChunkGenerator>(
+    cache: &mut ChunkCache<G>,
+    coord: ChunkCoord,
+    world_seed: u64,
+) -> Territories {
+    let nations = Nations::new(world_seed);
+    let size = cache.chunk_size();
+
+    let mut owners = Vec::new();
+    // Terrain of the chunk and the neighbours its apron reaches into, indexed by [dy][dx]
+    let mut terrain: [[Option<TerrainGrid>; 2]; 2] = Default::default();
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let neighbour = ChunkCoord::new(coord.x.wrapping_add(dx), coord.y.wrapping_add(dy));
+            let data = cache.get_or_generate(neighbour);
+            if dx >= 0 && dy >= 0 {
+                terrain[dy as usize][dx as usize] = Some(data.terrain.clone());
+            }
+            let (nx, ny) = neighbour.origin(size);
+            for c in &data.cities {
+                let city = (nx.wrapping_add(c.grid_x), ny.wrapping_add(c.grid_y));
+                owners.push(TerritoryOwner {
+                    city,
+                    city_seed: c.seed,
+                    nation: nations.nation_of(city.0, city.1),
+                });
+            }
+        }
+    }
+    // Canonical order so owner indices do not depend on generation order
+    owners.sort_by_key(|o| o.city);
+
+    // Ownership including a one-cell apron on the right and bottom for border tests
+    let (origin_x, origin_y) = coord.origin(size);
+    let span = size + 1;
+    let max_sq = max_territory_radius(size).pow(2);
+    let mut grid = Vec::with_capacity((span * span) as usize);
+    for gy in 0..span {
+        for gx in 0..span {
+            let (x, y) = (origin_x.wrapping_add(gx), origin_y.wrapping_add(gy));
+            let biomes = terrain[(gy / size) as usize][(gx / size) as usize]
+                .as_ref()
+                .expect("apron terrain collected");
+            if biomes.get(gx % size, gy % size).is_water() {
+                grid.push(None);
+                continue;
+            }
+            let nearest = owners
+                .iter()
+                .enumerate()
+                .map(|(i, o)| {
+                    let dx = o.city.0 as i64 - x as i64;
+                    let dy = o.city.1 as i64 - y as i64;
+                    let d = dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy));
+                    (d, i)
+                })
+                .filter(|&(d, _)| d <= max_sq)
+                .min();
+            grid.push(nearest.map(|(_, i)| i));
+        }
+    }
+
+    let at = |gx: i32, gy: i32| grid[(gy * span + gx) as usize];
+    let nation = |owner: Option<usize>| owner.map(|i| owners[i].nation);
+    let mut borders = Vec::new();
+    for gy in 0..size {
+        for gx in 0..size {
+            let here = at(gx, gy);
+            let (x, y) = (origin_x.wrapping_add(gx), origin_y.wrapping_add(gy));
+            if here.is_none() {
+                continue;
+            }
+            // Right edge
+            let right = at(gx + 1, gy);
+            if right.is_some() && here != right {
+                borders.push(Border {
+                    from: (x.wrapping_add(1), y),
+                    to: (x.wrapping_add(1), y.wrapping_add(1)),
+                    national: nation(here) != nation(right),
+                });
+            }
+            // Bottom edge
+            let below = at(gx, gy + 1);
+            if below.is_some() && here != below {
+                borders.push(Border {
+                    from: (x, y.wrapping_add(1)),
+                    to: (x.wrapping_add(1), y.wrapping_add(1)),
+                    national: nation(here) != nation(below),
+                });
+            }
+        }
+    }
+
+    // Compact the owner table to those actually present in the chunk
+    let mut used: Vec<Option<u16>> = vec![None; owners.len()];
+    let mut compact = Vec::new();
+    let mut cells = Vec::with_capacity((size * size) as usize);
+    for gy in 0..size {
+        for gx in 0..size {
+            cells.push(at(gx, gy).map(|i| {
+                *used[i].get_or_insert_with(|| {
+                    compact.push(owners[i]);
+                    (compact.len() - 1) as u16
+                })
+            }));
+        }
+    }
+
+    Territories {
+        coord,
+        size,
+        owners: compact,
+        cells,
+        borders: merge_borders(borders),
+    }
+}
+
+/// Join collinear, touching segments of the same kind into longer runs
+fn merge_borders(mut borders: Vec<Border>) -> Vec<Border> {
+    // Vertical segments first, grouped by x then y; horizontal by y then x
+    borders.sort_by_key(|b| {
+        let vertical = b.from.0 == b.to.0;
+        if vertical {
+            (0, b.from.0, b.from.1, b.national)
+        } else {
+            (1, b.from.1, b.from.0, b.national)
+        }
+    });
+
+    let mut merged: Vec<Border> = Vec::with_capacity(borders.len());
+    for b in borders {
+        if let Some(last) = merged.last_mut() {
+            let same_line = (last.from.0 == last.to.0) == (b.from.0 == b.to.0);
+            if same_line && last.to == b.from && last.national == b.national {
+                last.to = b.to;
+                continue;
+            }
+        }
+        merged.push(b);
+    }
+    merged
+}
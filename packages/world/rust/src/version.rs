@@ -0,0 +1,94 @@
+//! Generator version registry
+//!
+//! Every world records the generator version it was created with, and each
+//! version's output is frozen once released. Improvements to generation ship
+//! as a new version, so existing worlds never silently reshuffle. The default
+//! configuration stays on version 1; later versions apply only when a
+//! configuration selects them.
+
+use crate::chunk::ChunkCoord;
+use crate::hash::hash64;
+
+/// Independent RNG streams drawn per chunk
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SeedLayer {
+    /// Uniform city scatter
+    Cities = 0,
+    /// Blue-noise candidates
+    PoissonCandidates = 1,
+}
+
+/// Keeps the Poisson-disc stream apart from the city stream in the Dart seeding scheme
+const POISSON_STREAM_SALT: u64 = 0x504F_4953_534F_4E21;
+
+/// Chunk seed of the original Dart implementation
+pub fn dart_chunk_seed(world_seed: u64, coord: ChunkCoord) -> u64 {
+    // Dart: worldSeed ^ (coord.x * 73856093) ^ (coord.y * 19349663)
+    let seed = (world_seed as i64)
+        ^ ((coord.x as i64).wrapping_mul(73856093))
+        ^ ((coord.y as i64).wrapping_mul(19349663));
+    seed as u64
+}
+
+/// Well-mixed chunk seed: 64-bit hash of (world seed, x, y, layer)
+pub fn hashed_chunk_seed(world_seed: u64, coord: ChunkCoord, layer: SeedLayer) -> u64 {
+    hash64(world_seed, coord.x, coord.y, layer as u32)
+}
+
+/// A released chunk generation algorithm
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GeneratorVersion {
+    /// Original Dart algorithm: uniform scatter, cities only
+    DartScatter = 1,
+    /// Terrain, density field, rivers and lakes, regions and points of interest
+    Layered = 2,
+    /// As `Layered`, with chunk seeds from a well-mixed 64-bit hash
+    HashedSeeds = 3,
+}
+
+impl GeneratorVersion {
+    /// All versions, oldest first
+    pub const ALL: [GeneratorVersion; 3] = [
+        GeneratorVersion::DartScatter,
+        GeneratorVersion::Layered,
+        GeneratorVersion::HashedSeeds,
+    ];
+
+    /// Newest version, for configurations that opt in to it
+    pub const LATEST: GeneratorVersion = GeneratorVersion::HashedSeeds;
+
+    pub fn from_number(number: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|v| v.number() == number)
+    }
+
+    pub fn number(&self) -> u32 {
+        *self as u32
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GeneratorVersion::DartScatter => "dart_scatter",
+            GeneratorVersion::Layered => "layered",
+            GeneratorVersion::HashedSeeds => "hashed_seeds",
+        }
+    }
+
+    /// Whether the version supports placement strategies other than uniform
+    pub fn supports_placement(&self) -> bool {
+        match self {
+            GeneratorVersion::DartScatter => false,
+            GeneratorVersion::Layered | GeneratorVersion::HashedSeeds => true,
+        }
+    }
+
+    /// RNG seed of one stream of a chunk
+    pub fn chunk_seed(&self, world_seed: u64, coord: ChunkCoord, layer: SeedLayer) -> u64 {
+        match (self, layer) {
+            (GeneratorVersion::HashedSeeds, _) => hashed_chunk_seed(world_seed, coord, layer),
+            (_, SeedLayer::Cities) => dart_chunk_seed(world_seed, coord),
+            (_, SeedLayer::PoissonCandidates) => {
+                dart_chunk_seed(world_seed, coord) ^ POISSON_STREAM_SALT
+            }
+        }
+    }
+}
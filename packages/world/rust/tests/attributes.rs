@@ -0,0 +1,100 @@
+//! City identity derived from the seed alone.
+
+use opengrid_world::attributes::city_name;
+use opengrid_world::{CityAttributes, FoundingEra, SizeClass, WorldGenerator};
+
+/// Shared with the Dart client and the AO process; changing these renames every city
+#[test]
+fn known_seeds_expand_identically() {
+    let expected = [
+        (1, "Ralovor", 55, SizeClass::Village, FoundingEra::Medieval),
+        (
+            14,
+            "Roscorcor",
+            340_181,
+            SizeClass::Metropolis,
+            FoundingEra::Medieval,
+        ),
+        (
+            1000,
+            "Samaran",
+            4_475,
+            SizeClass::Town,
+            FoundingEra::Industrial,
+        ),
+        (
+            12345,
+            "Corpelhal",
+            1_352,
+            SizeClass::Village,
+            FoundingEra::Modern,
+        ),
+    ];
+    for (seed, name, population, size_class, founding_era) in expected {
+        assert_eq!(
+            CityAttributes::from_seed(seed),
+            CityAttributes {
+                name: name.to_string(),
+                population,
+                size_class,
+                founding_era,
+            }
+        );
+    }
+}
+
+#[test]
+fn populations_fit_their_size_class() {
+    let mut classes = Vec::new();
+    let mut eras = Vec::new();
+    for seed in (0..0x4000_0000).step_by(104_729) {
+        let attributes = CityAttributes::from_seed(seed);
+        let (min, max) = attributes.size_class.population_range();
+        assert!((min..=max).contains(&attributes.population));
+        classes.push(attributes.size_class);
+        eras.push(attributes.founding_era);
+    }
+
+    let share =
+        |class| classes.iter().filter(|&&c| c == class).count() as f64 / classes.len() as f64;
+    assert!((share(SizeClass::Village) - 0.70).abs() < 0.03);
+    assert!((share(SizeClass::Town) - 0.25).abs() < 0.03);
+    assert!((share(SizeClass::Metropolis) - 0.05).abs() < 0.02);
+    for era in [
+        FoundingEra::Ancient,
+        FoundingEra::Medieval,
+        FoundingEra::Industrial,
+        FoundingEra::Modern,
+    ] {
+        assert!(eras.contains(&era));
+    }
+}
+
+#[test]
+fn names_are_two_or_three_capitalised_syllables() {
+    for seed in 0..2000 {
+        let name = city_name(seed);
+        assert!((4..=12).contains(&name.len()), "{}", name);
+        assert!(
+            name.starts_with(|c: char| c.is_ascii_uppercase()),
+            "{}",
+            name
+        );
+        assert!(
+            name[1..].chars().all(|c| c.is_ascii_lowercase()),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn world_generator_exposes_the_same_identity() {
+    let world = WorldGenerator::new(99);
+    for seed in [0, 1, 14, 0x3FFF_FFFF] {
+        assert_eq!(
+            world.get_city_attributes(seed),
+            CityAttributes::from_seed(seed)
+        );
+    }
+}
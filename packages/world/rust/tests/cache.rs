@@ -0,0 +1,133 @@
+//! ChunkCache recency and statistics.
+
+mod common;
+
+use opengrid_world::rng::Pcg32;
+use opengrid_world::{
+    CacheStats, ChunkCache, ChunkCoord, GeneratorVersion, PlacementStrategy, WorldConfig,
+};
+
+/// Smallest allowed cache over the cheap version 1 generator
+fn small_cache() -> ChunkCache {
+    let config = WorldConfig {
+        cache_capacity: 9,
+        ..common::config(GeneratorVersion::DartScatter)
+    };
+    ChunkCache::with_config(7, config).unwrap()
+}
+
+fn coord(i: i32) -> ChunkCoord {
+    ChunkCoord::new(i % 7 - 3, i / 7 - 2)
+}
+
+#[test]
+fn evicts_the_least_recently_used_chunk_within_a_frame() {
+    let mut cache = small_cache();
+    for i in 0..9 {
+        cache.get_or_generate(coord(i));
+    }
+    cache.get_or_generate(coord(0));
+    cache.get_or_generate(coord(9));
+
+    assert_eq!(cache.evicted_this_frame(), [coord(1)]);
+    assert!(cache.get(coord(0)).is_some());
+    assert!(cache.get(coord(1)).is_none());
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 10,
+            evictions: 1
+        }
+    );
+
+    // Peeking leaves recency alone
+    assert!(cache.get(coord(2)).is_some());
+    cache.get_or_generate(coord(10));
+    assert_eq!(cache.evicted_this_frame(), [coord(1), coord(2)]);
+
+    cache.advance_frame();
+    assert!(cache.evicted_this_frame().is_empty());
+    assert_eq!(cache.frame(), 1);
+    assert_eq!(cache.get_or_generate(coord(3)).last_used, 1);
+    assert_eq!(cache.get_or_generate(coord(3)).generated_at, 0);
+}
+
+#[test]
+fn matches_a_reference_lru() {
+    let mut cache = small_cache();
+    let mut reference: Vec<ChunkCoord> = Vec::new();
+    let mut expected = CacheStats::default();
+    let mut rng = Pcg32::from_seed(50);
+
+    for step in 0..5000 {
+        if step % 97 == 0 {
+            cache.advance_frame();
+        }
+        let c = coord(rng.range(0, 20));
+        match reference.iter().position(|&r| r == c) {
+            Some(i) => {
+                reference.remove(i);
+                expected.hits += 1;
+            }
+            None => expected.misses += 1,
+        }
+        reference.push(c);
+        if reference.len() > 9 {
+            let evicted = reference.remove(0);
+            expected.evictions += 1;
+            assert_eq!(cache.get_or_generate(c).coord, c);
+            assert_eq!(cache.evicted_this_frame().last(), Some(&evicted));
+        } else {
+            cache.get_or_generate(c);
+        }
+        assert_eq!(cache.cached_count(), reference.len());
+        assert!(reference.iter().all(|&r| cache.get(r).is_some()));
+    }
+    assert_eq!(cache.stats(), expected);
+    assert!(expected.hit_rate() > 0.0 && expected.hit_rate() < 1.0);
+
+    cache.reset_stats();
+    assert_eq!(cache.stats(), CacheStats::default());
+    assert_eq!(cache.stats().hit_rate(), 0.0);
+}
+
+#[test]
+fn capacity_changes_evict_down_to_the_new_size() {
+    let mut cache = small_cache();
+    cache.set_capacity(20);
+    for i in 0..15 {
+        cache.get_or_generate(coord(i));
+    }
+    assert_eq!(cache.cached_count(), 15);
+
+    cache.set_capacity(10);
+    assert_eq!(cache.capacity(), 10);
+    assert_eq!(cache.cached_count(), 10);
+    assert_eq!(cache.evicted_this_frame(), (0..5).map(coord).collect::<Vec<_>>());
+
+    // Never below the 3x3 neighbourhood roads and territories need
+    cache.set_capacity(1);
+    assert_eq!(cache.capacity(), 9);
+}
+
+#[test]
+fn strategy_changes_count_the_chunks_they_drop() {
+    // Version 1 ignores placement, so switch strategies on a layered world
+    let mut cache = common::cache(7, GeneratorVersion::Layered);
+    for i in 0..4 {
+        cache.get_or_generate(coord(i));
+    }
+    cache.advance_frame();
+    cache.set_strategy(PlacementStrategy::Uniform).unwrap();
+    assert_eq!(cache.stats().evictions, 0);
+
+    let blue_noise = PlacementStrategy::PoissonDisc { min_distance: 3.0 };
+    cache.set_strategy(blue_noise).unwrap();
+    assert_eq!(cache.cached_count(), 0);
+    assert_eq!(cache.stats().evictions, 4);
+    assert_eq!(
+        cache.evicted_this_frame().len() as u64,
+        cache.stats().evictions
+    );
+}
@@ -0,0 +1,30 @@
+//! Worlds shared by the integration tests. Each test crate uses a subset.
+#![allow(dead_code)]
+
+use opengrid_world::{ChunkCache, GeneratorVersion, StandardGenerator, WorldConfig};
+
+/// Default configuration on a generator version
+pub fn config(version: GeneratorVersion) -> WorldConfig {
+    WorldConfig {
+        generator_version: version.number(),
+        ..WorldConfig::default()
+    }
+}
+
+pub fn generator(world_seed: u64, version: GeneratorVersion) -> StandardGenerator {
+    StandardGenerator::new(world_seed, config(version)).unwrap()
+}
+
+pub fn cache(world_seed: u64, version: GeneratorVersion) -> ChunkCache {
+    ChunkCache::with_config(world_seed, config(version)).unwrap()
+}
+
+/// Generator of a world with terrain, water, regions and POIs
+pub fn layered_generator(world_seed: u64) -> StandardGenerator {
+    generator(world_seed, GeneratorVersion::HashedSeeds)
+}
+
+/// Cache over a world with terrain, water, regions and POIs
+pub fn layered_cache(world_seed: u64) -> ChunkCache {
+    cache(world_seed, GeneratorVersion::HashedSeeds)
+}
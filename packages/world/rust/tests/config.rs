@@ -0,0 +1,216 @@
+//! World configuration: JSON, validation and the ruleset hash.
+
+use opengrid_world::{
+    ChunkCache, ConfigError, GeneratorVersion, PlacementStrategy, StandardGenerator, WorldConfig,
+};
+
+fn blue_noise() -> WorldConfig {
+    WorldConfig {
+        chunk_size: 48,
+        city_density: 0.05,
+        placement: PlacementStrategy::PoissonDisc { min_distance: 3.5 },
+        cache_capacity: 64,
+        generator_version: GeneratorVersion::HashedSeeds.number(),
+    }
+}
+
+#[test]
+fn json_round_trips() {
+    let config = blue_noise();
+    assert_eq!(WorldConfig::from_json(&config.to_json()), Ok(config));
+    assert_eq!(
+        WorldConfig::from_json(&WorldConfig::default().to_json()),
+        Ok(WorldConfig::default())
+    );
+}
+
+#[test]
+fn missing_fields_take_defaults() {
+    let config = WorldConfig::from_json(r#"{"chunk_size": 48}"#).unwrap();
+    assert_eq!(
+        config,
+        WorldConfig {
+            chunk_size: 48,
+            ..WorldConfig::default()
+        }
+    );
+    assert_eq!(WorldConfig::from_json("{}"), Ok(WorldConfig::default()));
+
+    let config = WorldConfig::from_json(
+        r#"{"placement": {"kind": "blue_noise", "min_distance": 3.5}, "generator_version": 3}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        config.placement,
+        PlacementStrategy::PoissonDisc { min_distance: 3.5 }
+    );
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let base = blue_noise();
+    let cases = [
+        (
+            WorldConfig {
+                chunk_size: 16,
+                ..base
+            },
+            ConfigError::ChunkSize(16),
+        ),
+        (
+            WorldConfig {
+                city_density: 0.0,
+                ..base
+            },
+            ConfigError::CityDensity(0.0),
+        ),
+        (
+            WorldConfig {
+                placement: PlacementStrategy::PoissonDisc { min_distance: 0.0 },
+                ..base
+            },
+            ConfigError::MinDistance(0.0),
+        ),
+        (
+            WorldConfig {
+                placement: PlacementStrategy::PoissonDisc { min_distance: 49.0 },
+                ..base
+            },
+            ConfigError::MinDistance(49.0),
+        ),
+        (
+            WorldConfig {
+                cache_capacity: 8,
+                ..base
+            },
+            ConfigError::CacheCapacity(8),
+        ),
+        (
+            WorldConfig {
+                generator_version: 99,
+                ..base
+            },
+            ConfigError::GeneratorVersion(99),
+        ),
+        (
+            WorldConfig {
+                generator_version: GeneratorVersion::DartScatter.number(),
+                ..base
+            },
+            ConfigError::Placement(1),
+        ),
+    ];
+    for (config, error) in cases {
+        assert_eq!(config.validate(), Err(error.clone()));
+        assert_eq!(
+            WorldConfig::from_json(&config.to_json()),
+            Err(error.clone())
+        );
+        assert_eq!(StandardGenerator::new(1, config).err(), Some(error.clone()));
+        assert_eq!(ChunkCache::with_config(1, config).err(), Some(error));
+    }
+
+    let nan = WorldConfig {
+        city_density: f64::NAN,
+        ..base
+    };
+    assert!(matches!(nan.validate(), Err(ConfigError::CityDensity(d)) if d.is_nan()));
+    assert!(matches!(
+        WorldConfig::from_json("{\"chunk_size\": \"big\"}"),
+        Err(ConfigError::Parse(_))
+    ));
+}
+
+#[test]
+fn placement_errors_name_the_versions_that_support_it() {
+    let error = ConfigError::Placement(GeneratorVersion::DartScatter.number());
+    assert_eq!(
+        error.to_string(),
+        "generator version 1 only supports uniform placement; other strategies need a config \
+         with generator_version 2 or later"
+    );
+    let mut cache = ChunkCache::new(1);
+    let blue_noise = PlacementStrategy::PoissonDisc { min_distance: 3.0 };
+    assert_eq!(cache.set_strategy(blue_noise), Err(error));
+}
+
+#[test]
+fn placement_changes_are_validated() {
+    let mut generator = StandardGenerator::new(1, blue_noise()).unwrap();
+    let too_far = PlacementStrategy::PoissonDisc { min_distance: 64.0 };
+    assert_eq!(
+        generator.set_placement(too_far),
+        Err(ConfigError::MinDistance(64.0))
+    );
+    assert_eq!(*generator.config(), blue_noise());
+    assert_eq!(generator.set_placement(PlacementStrategy::Uniform), Ok(()));
+    assert_eq!(generator.config().placement, PlacementStrategy::Uniform);
+
+    assert!(ChunkCache::with_strategy(1, PlacementStrategy::Uniform).is_ok());
+    assert!(matches!(
+        ChunkCache::with_strategy(1, PlacementStrategy::PoissonDisc { min_distance: f64::NAN }),
+        Err(ConfigError::MinDistance(d)) if d.is_nan()
+    ));
+}
+
+#[test]
+fn ruleset_hash_is_stable() {
+    let config = blue_noise();
+    assert_eq!(config.ruleset_id(), "735898c1e72062b2");
+    assert_eq!(
+        config.ruleset_id(),
+        format!("{:016x}", config.ruleset_hash())
+    );
+
+    // The cache capacity never changes the world
+    let roomier = WorldConfig {
+        cache_capacity: 4096,
+        ..config
+    };
+    assert_eq!(roomier.ruleset_hash(), config.ruleset_hash());
+
+    // Everything else does
+    let variants = [
+        WorldConfig {
+            chunk_size: 64,
+            ..config
+        },
+        WorldConfig {
+            city_density: 0.06,
+            ..config
+        },
+        WorldConfig {
+            placement: PlacementStrategy::PoissonDisc { min_distance: 4.0 },
+            ..config
+        },
+        WorldConfig {
+            placement: PlacementStrategy::Uniform,
+            ..config
+        },
+        WorldConfig {
+            generator_version: GeneratorVersion::Layered.number(),
+            ..config
+        },
+    ];
+    for variant in variants {
+        assert_ne!(
+            variant.ruleset_hash(),
+            config.ruleset_hash(),
+            "{:?}",
+            variant
+        );
+    }
+}
+
+#[test]
+fn capacity_changes_show_in_the_config() {
+    let mut cache = ChunkCache::with_config(1, blue_noise()).unwrap();
+    cache.set_capacity(500);
+    assert_eq!(cache.config().cache_capacity, 500);
+    assert_eq!(cache.config().ruleset_hash(), blue_noise().ruleset_hash());
+
+    // Clamped like the capacity itself
+    cache.set_capacity(1);
+    assert_eq!(cache.config().cache_capacity, cache.capacity());
+    assert_eq!(cache.config().validate(), Ok(()));
+}
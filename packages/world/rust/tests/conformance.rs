@@ -0,0 +1,181 @@
+//! Cross-implementation conformance vectors
+//!
+//! `tests/vectors/v<N>.json` hold the cities generated for a spread of seeds
+//! (64-bit and phrase seeds included) and chunk coordinates (negatives and
+//! extremes included) by each generator version. Layered versions sample terrain and water on i32 world cells; the
+//! last chunks inside that range and the empty ocean chunks past it are pinned
+//! too. The Dart original and the AO Lua process are checked against the same
+//! files. Each vector sits on its own line in a fixed key order, so the
+//! files are canonical and diff cleanly.
+//!
+//! Regenerate after adding a generator version with
+//! `OPENGRID_BLESS=1 cargo test --test conformance`.
+
+use opengrid_world::{ChunkCache, ChunkCoord, GeneratorVersion, WorldConfig, WorldSeed};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+const SEEDS: [u64; 11] = [
+    0,
+    1,
+    42,
+    12345,
+    0x7FFF_FFFF,
+    0x8000_0000,
+    0xDEAD_BEEF,
+    0xFFFF_FFFF,
+    1 << 32 | 12345,
+    1 << 63,
+    u64::MAX,
+];
+
+/// Phrase seeds; their vectors record the phrase next to the hashed value
+const PHRASES: [&str; 1] = ["hello world"];
+
+const COORDS: [(i32, i32); 14] = [
+    (0, 0),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (0, -1),
+    (-1, -1),
+    (63, -64),
+    (-1000, 1000),
+    (1 << 20, -(1 << 20)),
+    // The last chunks whose 64 cells fit in i32, and the first past them
+    ((1 << 25) - 1, -(1 << 25)),
+    (1 << 25, -(1 << 25) - 1),
+    (i32::MAX, i32::MAX),
+    (i32::MIN, i32::MIN),
+    (i32::MIN, i32::MAX),
+];
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct VectorFile {
+    generator_version: u32,
+    chunk_size: i32,
+    city_density: f64,
+    vectors: Vec<Vector>,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Vector {
+    #[serde(default)]
+    phrase: Option<String>,
+    seed: u64,
+    chunk_x: i32,
+    chunk_y: i32,
+    /// [grid_x, grid_y, seed] in generation order
+    cities: Vec<(i32, i32, u32)>,
+}
+
+fn path(version: GeneratorVersion) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/vectors")
+        .join(format!("v{}.json", version.number()))
+}
+
+/// Vectors as generated by this implementation
+fn generate(version: GeneratorVersion) -> VectorFile {
+    let config = WorldConfig {
+        generator_version: version.number(),
+        ..WorldConfig::default()
+    };
+    let mut vectors = Vec::new();
+    let phrases = PHRASES.map(|p| (Some(p), WorldSeed::parse(p).unwrap().value()));
+    for (phrase, seed) in SEEDS.map(|s| (None, s)).into_iter().chain(phrases) {
+        let mut cache = ChunkCache::with_config(seed, config).unwrap();
+        for (chunk_x, chunk_y) in COORDS {
+            let data = cache.get_or_generate(ChunkCoord::new(chunk_x, chunk_y));
+            vectors.push(Vector {
+                phrase: phrase.map(String::from),
+                seed,
+                chunk_x,
+                chunk_y,
+                cities: data.cities.iter().map(|c| (c.grid_x, c.grid_y, c.seed)).collect(),
+            });
+        }
+    }
+    VectorFile {
+        generator_version: config.generator_version,
+        chunk_size: config.chunk_size,
+        city_density: config.city_density,
+        vectors,
+    }
+}
+
+/// Canonical text: header fields, then one vector per line
+fn emit(file: &VectorFile) -> String {
+    let mut out = String::from("{\n");
+    out += &format!("  \"generator_version\": {},\n", file.generator_version);
+    out += &format!("  \"chunk_size\": {},\n", file.chunk_size);
+    out += &format!("  \"city_density\": {},\n", file.city_density);
+    out += "  \"vectors\": [\n";
+    for (i, v) in file.vectors.iter().enumerate() {
+        let cities: Vec<String> =
+            v.cities.iter().map(|(x, y, s)| format!("[{},{},{}]", x, y, s)).collect();
+        out += "    {";
+        if let Some(phrase) = &v.phrase {
+            out += &format!("\"phrase\":{},", serde_json::to_string(phrase).unwrap());
+        }
+        out += &format!(
+            "\"seed\":{},\"chunk_x\":{},\"chunk_y\":{},\"cities\":[{}]}}",
+            v.seed,
+            v.chunk_x,
+            v.chunk_y,
+            cities.join(",")
+        );
+        out += if i + 1 < file.vectors.len() { ",\n" } else { "\n" };
+    }
+    out += "  ]\n}\n";
+    out
+}
+
+fn check(version: GeneratorVersion) {
+    let generated = generate(version);
+    let text = emit(&generated);
+    let path = path(version);
+    if std::env::var_os("OPENGRID_BLESS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &text).unwrap();
+        return;
+    }
+
+    let stored = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {} (run with OPENGRID_BLESS=1)", path.display(), e));
+    let expected: VectorFile = serde_json::from_str(&stored).unwrap();
+    assert_eq!(expected.generator_version, generated.generator_version);
+    assert_eq!(expected.chunk_size, generated.chunk_size);
+    assert_eq!(expected.city_density, generated.city_density);
+    assert_eq!(expected.vectors.len(), generated.vectors.len());
+    for (want, got) in expected.vectors.iter().zip(&generated.vectors) {
+        assert_eq!(
+            want, got,
+            "seed {} chunk ({}, {}) diverges",
+            want.seed, want.chunk_x, want.chunk_y
+        );
+    }
+    assert_eq!(stored, text, "{} is not in canonical form", path.display());
+}
+
+#[test]
+fn dart_scatter_vectors() {
+    check(GeneratorVersion::DartScatter);
+}
+
+#[test]
+fn layered_vectors() {
+    check(GeneratorVersion::Layered);
+}
+
+#[test]
+fn hashed_seeds_vectors() {
+    check(GeneratorVersion::HashedSeeds);
+}
+
+#[test]
+fn vectors_cover_every_version() {
+    for version in GeneratorVersion::ALL {
+        assert!(path(version).exists(), "missing {}", path(version).display());
+    }
+}
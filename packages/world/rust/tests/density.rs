@@ -0,0 +1,78 @@
+//! Density field shape and its effect on city placement.
+
+mod common;
+
+use opengrid_world::density::MAX_DENSITY_FACTOR;
+use opengrid_world::{ChunkCoord, ChunkGenerator, DensityField};
+
+/// Mean keep probability over the cells of a chunk
+fn chunk_density(field: &DensityField, coord: ChunkCoord) -> f64 {
+    let mut total = 0.0;
+    for gy in (0..64).step_by(4) {
+        for gx in (0..64).step_by(4) {
+            let (x, y) = (coord.x * 64 + gx, coord.y * 64 + gy);
+            total += field.keep_probability(x as f64 + 0.5, y as f64 + 0.5);
+        }
+    }
+    total / 256.0
+}
+
+#[test]
+fn field_has_heartlands_and_wastes() {
+    let field = DensityField::new(5);
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for y in (-8192..8192).step_by(32) {
+        for x in (-8192..8192).step_by(32) {
+            let p = field.keep_probability(x as f64, y as f64);
+            assert!((0.0..=1.0).contains(&p));
+            assert!(field.factor(x as f64, y as f64) <= MAX_DENSITY_FACTOR);
+            min = min.min(p);
+            max = max.max(p);
+        }
+    }
+    assert_eq!((min, max), (0.0, 1.0));
+}
+
+#[test]
+fn thinning_follows_the_field() {
+    let field = DensityField::new(5);
+    let (mut kept, mut expected) = (0.0, 0.0);
+    for y in -300..300 {
+        for x in -300..300 {
+            let p = field.keep_probability(x as f64 + 0.5, y as f64 + 0.5);
+            expected += p;
+            if field.keeps(x, y) {
+                kept += 1.0;
+                assert!(p > 0.0);
+            }
+            assert_eq!(field.keeps(x, y), field.keeps(x, y));
+        }
+    }
+    assert!((kept / expected - 1.0).abs() < 0.02);
+}
+
+#[test]
+fn dense_chunks_hold_more_cities() {
+    let generator = common::layered_generator(5);
+    let field = DensityField::new(5);
+    let mut chunks: Vec<(f64, usize)> = Vec::new();
+    for cy in -6..6 {
+        for cx in -6..6 {
+            let coord = ChunkCoord::new(cx, cy);
+            chunks.push((
+                chunk_density(&field, coord),
+                generator.generate(coord).cities.len(),
+            ));
+        }
+    }
+    chunks.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let quarter = chunks.len() / 4;
+    let mean =
+        |part: &[(f64, usize)]| part.iter().map(|c| c.1 as f64).sum::<f64>() / part.len() as f64;
+    let (sparse, dense) = (
+        mean(&chunks[..quarter]),
+        mean(&chunks[chunks.len() - quarter..]),
+    );
+    assert!(dense > 2.0 * sparse, "dense {} vs sparse {}", dense, sparse);
+}
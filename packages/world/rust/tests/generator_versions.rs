@@ -0,0 +1,183 @@
+//! Frozen output of every generator version. A failure here means a change
+//! reshuffles existing worlds: ship it as a new generator version instead.
+
+mod common;
+
+use opengrid_world::{
+    generate, ChunkCache, ChunkCoord, City, GeneratorVersion, WorldConfig, WorldGenerator,
+    WorldSeed,
+};
+
+const SEEDS: [u32; 6] = [0, 1, 42, 12345, 0x7FFF_FFFF, u32::MAX];
+
+/// Seeds using the high 32 bits, plus the value of a phrase seed
+fn wide_seeds() -> [u64; 4] {
+    let phrase = WorldSeed::parse("hello world").unwrap().value();
+    [1 << 32 | 12345, 1 << 63, u64::MAX, phrase]
+}
+
+const COORDS: [(i32, i32); 8] = [
+    (0, 0),
+    (-1, -1),
+    (1, 0),
+    (0, 1),
+    (-7, 13),
+    (1000, -1000),
+    (i32::MAX, i32::MIN),
+    (i32::MIN, i32::MAX),
+];
+
+/// FNV-1a over a stream of integers
+struct Digest(u64);
+
+impl Digest {
+    fn new() -> Self {
+        Digest(0xCBF2_9CE4_8422_2325)
+    }
+
+    fn push(&mut self, value: i64) {
+        for b in value.to_le_bytes() {
+            self.0 = (self.0 ^ b as u64).wrapping_mul(0x0000_0100_0000_01B3);
+        }
+    }
+}
+
+/// Digest of everything a chunk holds
+fn chunk_digest(cache: &mut ChunkCache, coord: ChunkCoord, digest: &mut Digest) {
+    let data = cache.get_or_generate(coord);
+    for city in &data.cities {
+        digest.push(city.grid_x as i64);
+        digest.push(city.grid_y as i64);
+        digest.push(city.seed as i64);
+    }
+    for biome in data.terrain.cells() {
+        digest.push(biome as i64);
+    }
+    for river in &data.rivers {
+        for &(x, y) in &river.points {
+            digest.push(x as i64);
+            digest.push(y as i64);
+        }
+    }
+    for poi in &data.pois {
+        digest.push(poi.kind as i64);
+        digest.push(poi.grid_x as i64);
+        digest.push(poi.grid_y as i64);
+        digest.push(poi.seed as i64);
+    }
+}
+
+#[test]
+fn dart_scatter_sample_is_frozen() {
+    let mut cache = common::cache(12345, GeneratorVersion::DartScatter);
+    let data = cache.get_or_generate(ChunkCoord::new(0, 0));
+    let first: Vec<_> = data
+        .cities
+        .iter()
+        .take(3)
+        .map(|c| (c.grid_x, c.grid_y, c.seed))
+        .collect();
+    assert_eq!(first, FROZEN_DART_SAMPLE);
+    assert_eq!(data.cities.len(), FROZEN_DART_SAMPLE_COUNT);
+}
+
+#[test]
+fn dart_scatter_is_frozen() {
+    let mut digest = Digest::new();
+    for seed in SEEDS {
+        let mut cache = common::cache(u64::from(seed), GeneratorVersion::DartScatter);
+        for (x, y) in COORDS {
+            chunk_digest(&mut cache, ChunkCoord::new(x, y), &mut digest);
+        }
+    }
+    assert_eq!(digest.0, FROZEN_DART_DIGEST);
+}
+
+#[test]
+fn dart_scatter_is_frozen_for_wide_seeds() {
+    let mut digest = Digest::new();
+    for seed in wide_seeds() {
+        let mut cache = common::cache(seed, GeneratorVersion::DartScatter);
+        for (x, y) in COORDS {
+            chunk_digest(&mut cache, ChunkCoord::new(x, y), &mut digest);
+        }
+    }
+    assert_eq!(digest.0, FROZEN_DART_WIDE_DIGEST);
+}
+
+/// Digest of a few chunks around the origin for 32-bit, 64-bit and phrase seeds
+fn layered_digest(version: GeneratorVersion) -> u64 {
+    let mut digest = Digest::new();
+    let seeds = [0, 42, u64::from(u32::MAX)].into_iter().chain(wide_seeds());
+    for seed in seeds {
+        let mut cache = common::cache(seed, version);
+        for (x, y) in [(0, 0), (-1, -1), (5, -3)] {
+            chunk_digest(&mut cache, ChunkCoord::new(x, y), &mut digest);
+        }
+    }
+    digest.0
+}
+
+#[test]
+fn layered_is_frozen() {
+    assert_eq!(layered_digest(GeneratorVersion::Layered), FROZEN_LAYERED_DIGEST);
+}
+
+#[test]
+fn hashed_seeds_is_frozen() {
+    assert_eq!(layered_digest(GeneratorVersion::HashedSeeds), FROZEN_HASHED_SEEDS_DIGEST);
+}
+
+#[test]
+fn legacy_entry_points_stay_on_dart_scatter() {
+    let sample = |cities: &[City]| -> Vec<_> {
+        cities
+            .iter()
+            .take(3)
+            .map(|c| (c.grid_x, c.grid_y, c.seed))
+            .collect()
+    };
+    let mut cache = ChunkCache::new(12345);
+    assert_eq!(cache.version(), GeneratorVersion::DartScatter);
+    assert_eq!(
+        sample(&cache.get_or_generate(ChunkCoord::new(0, 0)).cities),
+        FROZEN_DART_SAMPLE
+    );
+    assert_eq!(
+        sample(&generate(12345, ChunkCoord::new(0, 0)).cities),
+        FROZEN_DART_SAMPLE
+    );
+    assert_eq!(
+        WorldGenerator::new(12345).generator_version(),
+        GeneratorVersion::DartScatter.number()
+    );
+}
+
+#[test]
+fn every_version_is_registered() {
+    for version in GeneratorVersion::ALL {
+        assert_eq!(
+            GeneratorVersion::from_number(version.number()),
+            Some(version)
+        );
+    }
+    // Adding a version never moves the default
+    assert_eq!(
+        WorldConfig::default().generator_version,
+        GeneratorVersion::DartScatter.number()
+    );
+}
+
+/// Seed 12345, chunk (0, 0), as produced by the original Dart-compatible code
+const FROZEN_DART_SAMPLE: [(i32, i32, u32); 3] = [
+    (40, 25, 771_817_918),
+    (63, 23, 717_043_158),
+    (55, 18, 616_261_506),
+];
+const FROZEN_DART_SAMPLE_COUNT: usize = 81;
+const FROZEN_DART_DIGEST: u64 = 10_700_503_346_954_712_964;
+const FROZEN_DART_WIDE_DIGEST: u64 = 17_152_736_524_574_861_958;
+// Versions 2 and 3 have not shipped yet, so these were re-blessed when 64-bit and
+// phrase seeds joined the digest; once released they are as frozen as version 1
+const FROZEN_LAYERED_DIGEST: u64 = 7_351_510_592_590_975_548;
+const FROZEN_HASHED_SEEDS_DIGEST: u64 = 7_867_411_963_151_223_256;
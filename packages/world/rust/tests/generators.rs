@@ -0,0 +1,132 @@
+//! Chunk generators, with and without a cache.
+
+use opengrid_world::{
+    generate, roads_touching_chunk, ChunkCache, ChunkCoord, ChunkData, ChunkGenerator, City,
+    CompositeGenerator, FixtureGenerator, Road, StandardGenerator, WorldConfig, WorldGenerator,
+    CHUNK_SIZE,
+};
+
+fn cells(chunk: &ChunkData) -> Vec<(i32, i32, u32)> {
+    let mut cells: Vec<_> = chunk
+        .cities
+        .iter()
+        .map(|c| (c.grid_x, c.grid_y, c.seed))
+        .collect();
+    cells.sort();
+    cells
+}
+
+#[test]
+fn standard_generator_matches_cache() {
+    let generator = StandardGenerator::new(12345, WorldConfig::default()).unwrap();
+    let mut cache = ChunkCache::new(12345);
+    for coord in [ChunkCoord::new(0, 0), ChunkCoord::new(-3, 7)] {
+        let direct = generator.generate(coord);
+        let cached = cache.get_or_generate(coord);
+        assert_eq!(cells(&direct), cells(cached));
+        assert!(direct.terrain.cells().eq(cached.terrain.cells()));
+    }
+}
+
+#[test]
+fn stateless_generation_matches_world_generator() {
+    let mut world = WorldGenerator::new(777);
+    for (x, y) in [(0, 0), (-2, 5), (0, 0)] {
+        let chunk = generate(777, ChunkCoord::new(x, y));
+        let mut expected = Vec::new();
+        for city in &chunk.cities {
+            let (x, y) = city.world_position(&chunk.coord, chunk.size);
+            expected.push(x);
+            expected.push(y);
+            expected.push(city.seed as f64);
+        }
+        assert_eq!(world.get_cities_in_chunk(x, y), expected);
+    }
+}
+
+#[test]
+fn fixture_places_cities_in_their_chunks() {
+    let generator = FixtureGenerator::new(64)
+        .with_city(10, 20, 1)
+        .with_city(-1, -1, 2)
+        .with_city(-64, 0, 3)
+        .with_city(10, 20, 4);
+
+    assert_eq!(
+        cells(&generator.generate(ChunkCoord::new(0, 0))),
+        [(10, 20, 4)]
+    );
+    assert_eq!(
+        cells(&generator.generate(ChunkCoord::new(-1, -1))),
+        [(63, 63, 2)]
+    );
+    assert_eq!(
+        cells(&generator.generate(ChunkCoord::new(-1, 0))),
+        [(0, 0, 3)]
+    );
+    assert!(generator.generate(ChunkCoord::new(5, 5)).cities.is_empty());
+}
+
+#[test]
+fn cache_serves_any_generator() {
+    let generator = FixtureGenerator::new(64)
+        .with_city(10, 10, 1)
+        .with_city(40, 10, 2)
+        .with_city(70, 10, 3);
+    let mut cache = ChunkCache::with_generator(generator);
+
+    assert_eq!(cache.chunk_size(), 64);
+    assert_eq!(cache.get_or_generate(ChunkCoord::new(0, 0)).cities.len(), 2);
+
+    let mut roads = roads_touching_chunk(&mut cache, ChunkCoord::new(0, 0));
+    roads.sort();
+    assert_eq!(
+        roads,
+        [Road::new((10, 10), (40, 10)), Road::new((40, 10), (70, 10))]
+    );
+}
+
+#[test]
+fn composite_applies_layers_in_order() {
+    let base = FixtureGenerator::new(64).with_city(1, 1, 7);
+    let generator = CompositeGenerator::new(base)
+        .with_layer(|chunk: &mut ChunkData| {
+            chunk.cities.push(City {
+                grid_x: 2,
+                grid_y: 2,
+                seed: 8,
+            })
+        })
+        .with_layer(|chunk: &mut ChunkData| chunk.cities.retain(|c| c.seed != 7));
+
+    assert_eq!(
+        cells(&generator.generate(ChunkCoord::new(4, -4))),
+        [(2, 2, 8)]
+    );
+    let mut cache = ChunkCache::with_generator(generator);
+    assert_eq!(
+        cells(cache.get_or_generate(ChunkCoord::new(0, 0))),
+        [(2, 2, 8)]
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn legacy_city_positions_assume_the_default_chunk_size() {
+    let city = City {
+        grid_x: 16,
+        grid_y: 48,
+        seed: 1,
+    };
+    let coord = ChunkCoord::new(-2, 3);
+    assert_eq!(city.world_position(&coord, 64), (-112.0, 240.0));
+    assert_eq!(
+        (city.world_x(&coord), city.world_y(&coord)),
+        city.world_position(&coord, CHUNK_SIZE)
+    );
+    assert_eq!(
+        (city.local_x(), city.local_y()),
+        city.local_position(CHUNK_SIZE)
+    );
+    assert_eq!(city.local_position(64), (0.25, 0.75));
+}
@@ -0,0 +1,46 @@
+//! Rivers and lakes found per chunk.
+
+use opengrid_world::{ChunkCoord, Hydrology, TerrainField};
+
+#[test]
+fn shared_traces_match_fresh_ones() {
+    let warm = Hydrology::new(7, 32);
+    let mut rivers = 0;
+    for y in -3..3 {
+        for x in -3..3 {
+            let coord = ChunkCoord::new(x, y);
+            let fresh = Hydrology::new(7, 32).water_in_chunk(coord);
+            assert_eq!(warm.water_in_chunk(coord), fresh);
+            rivers += fresh.0.len();
+        }
+    }
+    assert!(rivers > 0);
+}
+
+#[test]
+fn rivers_run_downhill_from_their_source() {
+    let hydrology = Hydrology::new(7, 64);
+    let terrain = TerrainField::new(7);
+    let height = |(x, y): (i32, i32)| terrain.height(x as f64 + 0.5, y as f64 + 0.5);
+    let mut traced = 0;
+    for y in -8..8 {
+        for x in -8..8 {
+            let coord = ChunkCoord::new(x, y);
+            let Some((river, lake)) = hydrology.river(coord) else {
+                continue;
+            };
+            traced += 1;
+            assert_eq!(river.points[0], hydrology.source(coord).unwrap());
+            for step in river.points.windows(2) {
+                assert!(height(step[1]) < height(step[0]));
+                let (dx, dy) = (step[1].0 - step[0].0, step[1].1 - step[0].1);
+                assert!(dx.abs() <= 1 && dy.abs() <= 1);
+            }
+            assert_eq!(river.ends_in_lake, lake.is_some());
+            if let Some(lake) = lake {
+                assert_eq!(lake.cells[0], *river.points.last().unwrap());
+            }
+        }
+    }
+    assert!(traced > 0);
+}
@@ -0,0 +1,127 @@
+//! Blue-noise placement and its parameters.
+
+mod common;
+
+use opengrid_world::{
+    ChunkCache, ChunkCoord, ConfigError, GeneratorVersion, PlacementStrategy, RegionCoord,
+    WorldConfig,
+};
+
+fn blue_noise(min_distance: f64) -> ChunkCache {
+    let config = WorldConfig {
+        placement: PlacementStrategy::PoissonDisc { min_distance },
+        ..common::config(GeneratorVersion::HashedSeeds)
+    };
+    ChunkCache::with_config(26, config).unwrap()
+}
+
+#[test]
+fn separation_holds_across_chunk_borders() {
+    let min_distance = 4.5;
+    let mut cache = blue_noise(min_distance);
+    let mut cities = Vec::new();
+    for cy in -4..4 {
+        for cx in -4..4 {
+            let data = cache.get_or_generate(ChunkCoord::new(cx, cy));
+            cities.extend(
+                data.cities
+                    .iter()
+                    .map(|c| (cx * 64 + c.grid_x, cy * 64 + c.grid_y)),
+            );
+        }
+    }
+    assert!(cities.len() > 100);
+
+    // Capitals and trade hubs keep their distance too
+    let hubs: Vec<_> = [(-1, -1), (0, -1), (-1, 0), (0, 0)]
+        .into_iter()
+        .flat_map(|(x, y)| {
+            cache
+                .region_data(RegionCoord::new(x, y))
+                .hubs()
+                .collect::<Vec<_>>()
+        })
+        .filter(|&(x, y)| (-256..256).contains(&x) && (-256..256).contains(&y))
+        .collect();
+    assert!(!hubs.is_empty());
+    for hub in &hubs {
+        assert!(cities.contains(hub), "hub {:?} missing", hub);
+    }
+    for (i, a) in cities.iter().enumerate() {
+        for b in &cities[i + 1..] {
+            assert_ne!(a, b);
+            let d = ((a.0 - b.0) as f64).hypot((a.1 - b.1) as f64);
+            assert!(d >= min_distance, "{:?} and {:?}", a, b);
+        }
+    }
+}
+
+#[test]
+fn hubs_keep_wide_separations_across_region_borders() {
+    // Wider than two hub radii, so the hub margin has to grow with the separation
+    let min_distance = 60.0;
+    let cache = blue_noise(min_distance);
+    for world_seed in 0..8 {
+        let cache = ChunkCache::with_config(world_seed, cache.config()).unwrap();
+        let hubs: Vec<_> = (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| RegionCoord::new(x, y)))
+            .flat_map(|r| cache.region_data(r).hubs().collect::<Vec<_>>())
+            .collect();
+        for (i, a) in hubs.iter().enumerate() {
+            for b in &hubs[i + 1..] {
+                let d = ((a.0 - b.0) as f64).hypot((a.1 - b.1) as f64);
+                assert!(
+                    d >= min_distance,
+                    "seed {}: {:?} and {:?}",
+                    world_seed,
+                    a,
+                    b
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn strategy_names_need_a_positive_distance() {
+    assert_eq!(
+        PlacementStrategy::from_name("uniform", f64::NAN),
+        Ok(PlacementStrategy::Uniform)
+    );
+    assert_eq!(
+        PlacementStrategy::from_name("blue_noise", 2.0),
+        Ok(PlacementStrategy::PoissonDisc { min_distance: 2.0 })
+    );
+    for bad in [0.0, -1.0, f64::INFINITY] {
+        assert_eq!(
+            PlacementStrategy::from_name("poisson", bad),
+            Err(ConfigError::MinDistance(bad))
+        );
+    }
+    assert!(matches!(
+        PlacementStrategy::from_name("poisson", f64::NAN),
+        Err(ConfigError::MinDistance(d)) if d.is_nan()
+    ));
+    assert_eq!(
+        PlacementStrategy::from_name("grid", 1.0),
+        Err(ConfigError::UnknownPlacement("grid".into()))
+    );
+}
+
+#[test]
+fn invalid_strategies_leave_the_cache_alone() {
+    let mut cache = blue_noise(3.0);
+    cache.get_or_generate(ChunkCoord::new(0, 0));
+    for min_distance in [0.0, f64::NAN, 65.0] {
+        let strategy = PlacementStrategy::PoissonDisc { min_distance };
+        assert!(cache.set_strategy(strategy).is_err());
+    }
+    assert_eq!(
+        cache.strategy(),
+        PlacementStrategy::PoissonDisc { min_distance: 3.0 }
+    );
+    assert_eq!(cache.cached_count(), 1);
+
+    cache.set_strategy(PlacementStrategy::Uniform).unwrap();
+    assert_eq!(cache.cached_count(), 0);
+}
@@ -0,0 +1,111 @@
+//! Point-of-interest placement and seed-derived properties.
+
+mod common;
+
+use std::collections::{HashMap, HashSet};
+
+use opengrid_world::poi::place_pois;
+use opengrid_world::{
+    ChunkCoord, ChunkGenerator, GeneratorVersion, PoiKind, PoiProperties, TerrainField,
+};
+
+#[test]
+fn pois_take_free_land_cells() {
+    let generator = common::layered_generator(9);
+    let mut total = 0;
+    for cy in -3..3 {
+        for cx in -3..3 {
+            let coord = ChunkCoord::new(cx, cy);
+            let chunk = generator.generate(coord);
+            let (origin_x, origin_y) = coord.origin(chunk.size);
+            let cities: HashSet<_> = chunk.cities.iter().map(|c| (c.grid_x, c.grid_y)).collect();
+            let mut taken = HashSet::new();
+            for poi in &chunk.pois {
+                let cell = (poi.grid_x, poi.grid_y);
+                assert!((0..chunk.size).contains(&cell.0) && (0..chunk.size).contains(&cell.1));
+                assert!(!chunk.terrain.get(cell.0, cell.1).is_water());
+                assert!(!cities.contains(&cell));
+                let world = (origin_x + cell.0, origin_y + cell.1);
+                assert!(!chunk.rivers.iter().any(|r| r.points.contains(&world)));
+                assert!(taken.insert(cell), "two POIs at {:?}", cell);
+                assert_eq!(poi.seed & !0x3FFF_FFFF, 0);
+            }
+            total += chunk.pois.len();
+        }
+    }
+    assert!(total > 36);
+}
+
+#[test]
+fn cities_only_veto_poi_cells() {
+    let generator = common::layered_generator(9);
+    let field = TerrainField::new(9);
+    for cx in 0..8 {
+        let coord = ChunkCoord::new(cx, 2);
+        let chunk = generator.generate(coord);
+        let terrain = field.chunk_grid(coord, chunk.size);
+        // Without cities or rivers, every POI of the chunk is still placed, unmoved
+        let unconstrained = place_pois(9, coord, &terrain, &[], &[]);
+        for poi in &chunk.pois {
+            assert!(unconstrained.contains(poi), "{:?}", poi);
+        }
+        assert_eq!(place_pois(9, coord, &terrain, &[], &[]), unconstrained);
+    }
+}
+
+#[test]
+fn kinds_follow_their_frequencies() {
+    let generator = common::layered_generator(9);
+    let mut counts: HashMap<PoiKind, usize> = HashMap::new();
+    for cy in 0..10 {
+        for cx in 0..10 {
+            for poi in generator.generate(ChunkCoord::new(cx, cy)).pois {
+                *counts.entry(poi.kind).or_default() += 1;
+            }
+        }
+    }
+    let count = |kind| counts.get(&kind).copied().unwrap_or(0);
+    // Four attempts per kind at 1/2, 1/4, 1/10 and 1/4, minus water and collisions
+    assert!(count(PoiKind::Depot) > count(PoiKind::Market));
+    assert!(count(PoiKind::Depot) > count(PoiKind::Hazard));
+    assert!(count(PoiKind::Market) > count(PoiKind::Landmark));
+    assert!(count(PoiKind::Hazard) > count(PoiKind::Landmark));
+    assert!(count(PoiKind::Landmark) > 0);
+}
+
+#[test]
+fn dart_scatter_has_no_pois() {
+    let generator = common::generator(9, GeneratorVersion::DartScatter);
+    for cx in -2..2 {
+        assert!(generator.generate(ChunkCoord::new(cx, 0)).pois.is_empty());
+    }
+}
+
+#[test]
+fn properties_stay_in_range() {
+    for seed in 0..2_000u32 {
+        let seed = seed.wrapping_mul(0x9E37_79B9) & 0x3FFF_FFFF;
+        for kind in PoiKind::ALL {
+            let p = PoiProperties::from_seed(kind, seed);
+            assert_eq!(p, PoiProperties::from_seed(kind, seed));
+            assert_eq!(p.kind, kind);
+            assert!((1..=5).contains(&p.tier));
+            match kind {
+                PoiKind::Depot => {
+                    assert!(p.name.ends_with(" Depot"));
+                    assert!((p.tier * 200..p.tier * 200 + 200).contains(&p.value));
+                }
+                PoiKind::Market => {
+                    assert!(p.name.ends_with(" Market"));
+                    assert!((80..=120).contains(&p.value));
+                }
+                PoiKind::Landmark => {
+                    assert!((p.tier * 10..p.tier * 10 + 10).contains(&p.value))
+                }
+                PoiKind::Hazard => assert!((1..=4).contains(&p.value)),
+            }
+        }
+    }
+    assert_eq!(PoiKind::from_id(3), Some(PoiKind::Hazard));
+    assert_eq!(PoiKind::from_id(4), None);
+}
@@ -0,0 +1,150 @@
+//! City queries across chunk borders and negative coordinates.
+
+use opengrid_world::{
+    cities_in_radius, cities_in_rect, nearest_cities, ChunkCache, FixtureGenerator, QueryError,
+    WorldCity,
+};
+
+fn positions(cities: &[WorldCity]) -> Vec<(i32, i32)> {
+    cities.iter().map(|c| (c.x, c.y)).collect()
+}
+
+/// Cities straddling the borders of the four chunks around the origin
+fn fixture() -> ChunkCache<FixtureGenerator> {
+    let generator = FixtureGenerator::new(64)
+        .with_city(-1, -1, 1)
+        .with_city(0, 0, 2)
+        .with_city(63, -64, 3)
+        .with_city(-64, 63, 4)
+        .with_city(-10, 5, 5)
+        .with_city(200, -300, 6);
+    ChunkCache::with_generator(generator)
+}
+
+/// Every city of a standard world in a square of chunks around the origin
+fn all_cities(cache: &mut ChunkCache, chunks: i32) -> Vec<WorldCity> {
+    let (min, max) = ((-chunks * 64) as f64, (chunks * 64 - 1) as f64);
+    cities_in_rect(cache, min, min, max, max).unwrap()
+}
+
+#[test]
+fn rect_spans_chunks_and_negative_cells() {
+    let mut cache = fixture();
+    let cities = cities_in_rect(&mut cache, -64.0, -64.0, 63.0, 63.0).unwrap();
+    assert_eq!(
+        positions(&cities),
+        [(63, -64), (-1, -1), (0, 0), (-10, 5), (-64, 63)]
+    );
+
+    // Bounds are inclusive, and fractional bounds only admit whole cells inside
+    let cities = cities_in_rect(&mut cache, -1.0, -1.0, 0.0, 0.0).unwrap();
+    assert_eq!(positions(&cities), [(-1, -1), (0, 0)]);
+    let cities = cities_in_rect(&mut cache, -0.5, -0.5, 0.5, 0.5).unwrap();
+    assert_eq!(positions(&cities), [(0, 0)]);
+
+    let city = cities_in_rect(&mut cache, -1.0, -1.0, -1.0, -1.0).unwrap()[0];
+    assert_eq!(
+        (city.chunk.x, city.chunk.y, city.grid_x, city.grid_y),
+        (-1, -1, 63, 63)
+    );
+}
+
+#[test]
+fn radius_is_inclusive_and_sorted() {
+    let mut cache = fixture();
+    let cities = cities_in_radius(&mut cache, -0.5, -0.5, 11.0).unwrap();
+    assert_eq!(positions(&cities), [(-1, -1), (0, 0), (-10, 5)]);
+    let cities = cities_in_radius(&mut cache, 0.0, 0.0, 2.0f64.sqrt()).unwrap();
+    assert_eq!(positions(&cities), [(0, 0), (-1, -1)]);
+}
+
+#[test]
+fn nearest_searches_outwards() {
+    let mut cache = fixture();
+    let cities = nearest_cities(&mut cache, 190.0, -290.0, 2).unwrap();
+    assert_eq!(positions(&cities), [(200, -300), (63, -64)]);
+    assert!(nearest_cities(&mut cache, 0.0, 0.0, 0).unwrap().is_empty());
+
+    // An empty world yields nothing once the search budget is spent
+    let mut empty = ChunkCache::with_generator(FixtureGenerator::new(64));
+    assert!(nearest_cities(&mut empty, 0.0, 0.0, 3).unwrap().is_empty());
+}
+
+#[test]
+fn queries_match_brute_force_on_a_standard_world() {
+    let mut cache = ChunkCache::new(2024);
+    let everything = all_cities(&mut cache, 4);
+
+    let (min_x, min_y, max_x, max_y) = (-130.5, -20.0, 70.0, 129.0);
+    let expected: Vec<_> = everything
+        .iter()
+        .filter(|c| {
+            (min_x..=max_x).contains(&(c.x as f64)) && (min_y..=max_y).contains(&(c.y as f64))
+        })
+        .copied()
+        .collect();
+    assert_eq!(
+        cities_in_rect(&mut cache, min_x, min_y, max_x, max_y).unwrap(),
+        expected
+    );
+
+    let (x, y) = (-17.25, 40.5);
+    let mut by_distance = everything.clone();
+    by_distance.sort_by(|a, b| {
+        a.distance_to(x, y)
+            .total_cmp(&b.distance_to(x, y))
+            .then((a.y, a.x).cmp(&(b.y, b.x)))
+    });
+
+    let within: Vec<_> = by_distance
+        .iter()
+        .filter(|c| c.distance_to(x, y) <= 90.0)
+        .copied()
+        .collect();
+    assert_eq!(cities_in_radius(&mut cache, x, y, 90.0).unwrap(), within);
+
+    let nearest = nearest_cities(&mut cache, x, y, 25).unwrap();
+    assert_eq!(nearest, by_distance[..25]);
+}
+
+#[test]
+fn invalid_and_oversized_queries_are_rejected() {
+    let mut cache = fixture();
+    assert_eq!(
+        cities_in_rect(&mut cache, 10.0, 0.0, 0.0, 10.0),
+        Err(QueryError::InvalidArea)
+    );
+    assert_eq!(
+        cities_in_radius(&mut cache, 0.0, 0.0, -1.0),
+        Err(QueryError::InvalidArea)
+    );
+    assert_eq!(
+        nearest_cities(&mut cache, f64::NAN, 0.0, 1),
+        Err(QueryError::InvalidArea)
+    );
+    assert_eq!(
+        cities_in_rect(&mut cache, 0.0, 0.0, 1e12, 0.0),
+        Err(QueryError::InvalidArea)
+    );
+    // 33 x 33 chunks
+    let side = 64.0 * 33.0;
+    assert_eq!(
+        cities_in_rect(&mut cache, 0.0, 0.0, side - 1.0, side - 1.0),
+        Err(QueryError::TooManyChunks(33 * 33))
+    );
+}
+
+#[test]
+fn chunk_counts_past_u64_are_rejected() {
+    // One-cell chunks make both spans 2^32, whose product overflows u64
+    let mut cache = ChunkCache::with_generator(FixtureGenerator::new(1));
+    let (min, max) = (i32::MIN as f64, i32::MAX as f64);
+    assert_eq!(
+        cities_in_rect(&mut cache, min, min, max, max),
+        Err(QueryError::TooManyChunks(u64::MAX))
+    );
+    assert_eq!(
+        cities_in_rect(&mut cache, min, 0.0, max, 0.0),
+        Err(QueryError::TooManyChunks(1 << 32))
+    );
+}
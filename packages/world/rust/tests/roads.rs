@@ -0,0 +1,103 @@
+//! Road network agreement between chunks.
+
+mod common;
+
+use opengrid_world::{
+    cities_in_rect, max_road_length, roads_touching_chunk, ChunkCache, ChunkCoord, Road,
+};
+
+fn in_chunk(cell: (i32, i32), coord: ChunkCoord) -> bool {
+    ChunkCoord::containing(cell.0, cell.1, 64) == coord
+}
+
+/// Roads linking a city in `a` with a city in `b`
+fn shared(roads: &[Road], a: ChunkCoord, b: ChunkCoord) -> Vec<Road> {
+    roads
+        .iter()
+        .filter(|r| {
+            (in_chunk(r.from, a) && in_chunk(r.to, b)) || (in_chunk(r.from, b) && in_chunk(r.to, a))
+        })
+        .copied()
+        .collect()
+}
+
+#[test]
+fn neighbouring_chunks_agree_on_shared_roads() {
+    for world_seed in [3, 808] {
+        let mut cache = common::layered_cache(world_seed);
+        let mut links = 0;
+        for (x, y) in [(0, 0), (-1, -1), (2, -3)] {
+            let coord = ChunkCoord::new(x, y);
+            let roads = roads_touching_chunk(&mut cache, coord);
+            for r in &roads {
+                assert!(in_chunk(r.from, coord) || in_chunk(r.to, coord));
+            }
+            for (dx, dy) in [(1, 0), (0, 1), (1, 1), (-1, 1)] {
+                let neighbour = ChunkCoord::new(x + dx, y + dy);
+                // Generated from scratch, so neither side sees the other's cache
+                let theirs =
+                    roads_touching_chunk(&mut common::layered_cache(world_seed), neighbour);
+                let ours = shared(&roads, coord, neighbour);
+                assert_eq!(ours, shared(&theirs, coord, neighbour));
+                links += ours.len();
+            }
+        }
+        assert!(links > 0);
+    }
+}
+
+#[test]
+fn roads_form_a_bounded_relative_neighbourhood_graph() {
+    let mut cache = common::layered_cache(3);
+    let cities: Vec<(i32, i32)> = cities_in_rect(&mut cache, -192.0, -192.0, 191.0, 191.0)
+        .unwrap()
+        .iter()
+        .map(|c| (c.x, c.y))
+        .collect();
+    let dist_sq =
+        |a: (i32, i32), b: (i32, i32)| ((a.0 - b.0) as i64).pow(2) + ((a.1 - b.1) as i64).pow(2);
+
+    let coord = ChunkCoord::new(0, 0);
+    let roads = roads_touching_chunk(&mut cache, coord);
+    for road in &roads {
+        let d = dist_sq(road.from, road.to);
+        assert!(d <= max_road_length(64).pow(2));
+        for &r in &cities {
+            assert!(
+                dist_sq(road.from, r).max(dist_sq(road.to, r)) >= d,
+                "{:?} blocks {:?}",
+                r,
+                road
+            );
+        }
+    }
+
+    // Every unblocked pair within reach is linked
+    let own: Vec<_> = cities.iter().filter(|&&c| in_chunk(c, coord)).collect();
+    for &&p in &own {
+        for &q in &cities {
+            let d = dist_sq(p, q);
+            if d == 0 || d > max_road_length(64).pow(2) {
+                continue;
+            }
+            let blocked = cities
+                .iter()
+                .any(|&r| dist_sq(p, r) < d && dist_sq(q, r) < d);
+            assert_eq!(roads.contains(&Road::new(p, q)), !blocked);
+        }
+    }
+}
+
+#[test]
+fn roads_never_cross_the_edge_of_the_cell_range() {
+    // The last chunk whose cells fit in i32; its right neighbour wraps to i32::MIN
+    let edge = ChunkCoord::new(i32::MAX / 64, 0);
+    let mut cache = ChunkCache::new(42);
+    let roads = roads_touching_chunk(&mut cache, edge);
+    assert!(!roads.is_empty());
+    for r in roads {
+        let dx = r.from.0 as i64 - r.to.0 as i64;
+        let dy = r.from.1 as i64 - r.to.1 as i64;
+        assert!(dx * dx + dy * dy <= max_road_length(64).pow(2), "{:?}", r);
+    }
+}
@@ -0,0 +1,111 @@
+//! World seeds: parsing, canonical form, reproducibility of u32 seeds, and
+//! 64-bit field seeds.
+
+use opengrid_world::hash::mix32;
+use opengrid_world::seed::field_seed;
+use opengrid_world::{ChunkCache, ChunkCoord, SeedError, TerrainField, WorldConfig, WorldSeed};
+
+fn cities(world_seed: u64, coord: ChunkCoord) -> Vec<(i32, i32, u32)> {
+    let mut cache = ChunkCache::with_config(world_seed, WorldConfig::default()).unwrap();
+    cache
+        .get_or_generate(coord)
+        .cities
+        .iter()
+        .map(|c| (c.grid_x, c.grid_y, c.seed))
+        .collect()
+}
+
+#[test]
+fn numbers_parse_as_numeric_seeds() {
+    assert_eq!(
+        WorldSeed::parse("12345").unwrap(),
+        WorldSeed::from(12345u32)
+    );
+    assert_eq!(WorldSeed::parse(" 0 ").unwrap().value(), 0);
+    assert_eq!(
+        WorldSeed::parse("18446744073709551615").unwrap(),
+        WorldSeed::from(u64::MAX)
+    );
+    assert_eq!(WorldSeed::parse("12345").unwrap().phrase(), None);
+}
+
+#[test]
+fn phrases_hash_to_frozen_values() {
+    // Ports must reproduce these
+    assert_eq!(
+        WorldSeed::parse("hello world").unwrap().value(),
+        417524495691944273
+    );
+    assert_eq!(
+        WorldSeed::parse("opengrid").unwrap().value(),
+        15688438496619921972
+    );
+}
+
+#[test]
+fn phrases_ignore_spacing_and_ascii_case() {
+    let seed = WorldSeed::parse("  Hello \t  WORLD\n").unwrap();
+    assert_eq!(seed, WorldSeed::parse("hello world").unwrap());
+    assert_eq!(seed.phrase(), Some("hello world"));
+    assert_ne!(seed, WorldSeed::parse("helloworld").unwrap());
+}
+
+#[test]
+fn canonical_form_round_trips() {
+    for text in [
+        "0",
+        "4294967295",
+        "18446744073709551615",
+        "Hello World",
+        "-12",
+        "18446744073709551616",
+        "1 2 3",
+        "Zürich",
+    ] {
+        let seed = WorldSeed::parse(text).unwrap();
+        assert_eq!(
+            WorldSeed::parse(&seed.to_string()).unwrap(),
+            seed,
+            "{}",
+            text
+        );
+    }
+    assert_eq!(
+        WorldSeed::parse("  Two   Words ").unwrap().to_string(),
+        "two words"
+    );
+    assert_eq!(
+        WorldSeed::from(u64::MAX).to_string(),
+        "18446744073709551615"
+    );
+}
+
+#[test]
+fn blank_seeds_are_rejected() {
+    assert_eq!(WorldSeed::parse(""), Err(SeedError::Empty));
+    assert_eq!(WorldSeed::parse(" \t\n"), Err(SeedError::Empty));
+}
+
+#[test]
+fn field_seeds_keep_all_64_bits() {
+    for seed in [0, 1, 12345, u32::MAX as u64] {
+        assert_ne!(field_seed(seed, 1), field_seed(seed, 2));
+        assert_ne!(field_seed(seed, 1), field_seed(seed | 1 << 32, 1));
+        assert_ne!(field_seed(seed, 1), field_seed(seed | 1 << 63, 1));
+    }
+
+    // Folding the high word into the low one made this seed a twin of 12345
+    let twin = 1 << 32 | (12345 ^ mix32(1)) as u64;
+    let (a, b) = (TerrainField::new(12345), TerrainField::new(twin));
+    let differs = (0..64).any(|i| a.height(i as f64 * 7.5, 0.5) != b.height(i as f64 * 7.5, 0.5));
+    assert!(differs);
+}
+
+#[test]
+fn high_seed_bits_change_the_world() {
+    let coord = ChunkCoord::new(3, -2);
+    let low = cities(12345, coord);
+    assert_ne!(cities(1 << 32 | 12345, coord), low);
+    assert_ne!(cities(1 << 63 | 12345, coord), low);
+    assert_ne!(field_seed(1 << 32 | 12345, 1), field_seed(12345, 1));
+}
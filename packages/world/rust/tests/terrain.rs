@@ -0,0 +1,169 @@
+//! Terrain continuity and dry-land cities and hubs.
+
+mod common;
+
+use opengrid_world::{
+    Biome, ChunkCoord, ChunkGenerator, GeneratorVersion, Hydrology, PlacementStrategy, RegionCoord,
+    RegionData, TerrainField,
+};
+
+#[test]
+fn chunk_grids_sample_one_continuous_field() {
+    let field = TerrainField::new(321);
+    for (cx, cy) in [(0, 0), (-1, 0), (3, -2), (-5, -5)] {
+        let coord = ChunkCoord::new(cx, cy);
+        let grid = field.chunk_grid(coord, 64);
+        for gy in 0..64 {
+            for gx in 0..64 {
+                let biome = field.biome_at(cx * 64 + gx, cy * 64 + gy);
+                assert_eq!(grid.get(gx, gy), biome);
+            }
+        }
+    }
+
+    // Height changes gradually between neighbouring cells, chunk borders included
+    for y in -200..200 {
+        for x in [-65, -1, 0, 63, 64] {
+            let (a, b) = (
+                field.height(x as f64 + 0.5, y as f64),
+                field.height(x as f64 + 1.5, y as f64),
+            );
+            assert!((a - b).abs() < 0.05, "step at ({}, {})", x, y);
+        }
+    }
+}
+
+#[test]
+fn worlds_have_every_noise_biome() {
+    let field = TerrainField::new(2024);
+    let mut seen = Vec::new();
+    for y in (-4096..4096).step_by(16) {
+        for x in (-4096..4096).step_by(16) {
+            let biome = field.biome_at(x, y);
+            if !seen.contains(&biome) {
+                seen.push(biome);
+            }
+        }
+    }
+    for biome in [
+        Biome::Ocean,
+        Biome::Plains,
+        Biome::Forest,
+        Biome::Mountains,
+        Biome::Desert,
+    ] {
+        assert!(seen.contains(&biome), "{:?} missing", biome);
+    }
+    assert!(!seen.contains(&Biome::Lake));
+}
+
+#[test]
+fn version_one_chunks_store_no_terrain_cells() {
+    let mut cache = common::cache(5, GeneratorVersion::DartScatter);
+    let chunk = cache.get_or_generate(ChunkCoord::new(2, -1));
+    assert_eq!(chunk.terrain.uniform_biome(), Some(Biome::Plains));
+    assert_eq!(chunk.terrain.cells().len(), 64 * 64);
+    assert!(chunk.terrain.cells().all(|b| b == Biome::Plains));
+    assert_eq!(chunk.terrain.get(63, 63), Biome::Plains);
+
+    // Layered chunks keep a biome per cell
+    let mut layered = common::layered_cache(5);
+    assert_eq!(
+        layered
+            .get_or_generate(ChunkCoord::new(2, -1))
+            .terrain
+            .uniform_biome(),
+        None
+    );
+}
+
+#[test]
+fn chunks_past_the_cell_range_are_open_ocean() {
+    let mut cache = common::layered_cache(5);
+    let last = ChunkCoord::new((1 << 25) - 1, -(1 << 25));
+    assert!(last.cells_in_range(64));
+    assert_eq!(cache.get_or_generate(last).terrain.uniform_biome(), None);
+
+    for coord in [
+        ChunkCoord::new(1 << 25, 0),
+        ChunkCoord::new(0, -(1 << 25) - 1),
+        ChunkCoord::new(i32::MIN, i32::MAX),
+    ] {
+        assert!(!coord.cells_in_range(64));
+        let chunk = cache.get_or_generate(coord);
+        assert_eq!(chunk.terrain.uniform_biome(), Some(Biome::Ocean));
+        assert!(chunk.cities.is_empty() && chunk.rivers.is_empty() && chunk.pois.is_empty());
+    }
+}
+
+#[test]
+fn cities_stand_on_dry_land() {
+    for world_seed in [1, 77, 4242] {
+        let generator = common::layered_generator(world_seed);
+        let field = TerrainField::new(world_seed);
+        for cy in -3..3 {
+            for cx in -3..3 {
+                let coord = ChunkCoord::new(cx, cy);
+                let chunk = generator.generate(coord);
+                let (origin_x, origin_y) = (cx * chunk.size, cy * chunk.size);
+                for city in &chunk.cities {
+                    assert!(!chunk.terrain.get(city.grid_x, city.grid_y).is_water());
+                    let cell = (origin_x + city.grid_x, origin_y + city.grid_y);
+                    assert!(!chunk.rivers.iter().any(|r| r.points.contains(&cell)));
+                }
+
+                // The chunk keeps the noise biomes, apart from flooded lakes
+                let noise = field.chunk_grid(coord, chunk.size);
+                for (cell, biome) in chunk.terrain.cells().zip(noise.cells()) {
+                    assert!(cell == biome || cell == Biome::Lake);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hubs_avoid_sea_lakes_and_rivers() {
+    for world_seed in 0..24 {
+        let generator = common::layered_generator(world_seed);
+        let hydrology = Hydrology::new(world_seed, generator.chunk_size());
+        for (rx, ry) in [(0, 0), (-1, 0), (0, -1)] {
+            let region = generator.region_data(RegionCoord::new(rx, ry));
+            for (x, y) in region.hubs() {
+                assert!(
+                    !hydrology.is_water(x, y),
+                    "seed {}: hub {:?}",
+                    world_seed,
+                    (x, y)
+                );
+                let coord = ChunkCoord::containing(x, y, generator.chunk_size());
+                let chunk = generator.generate(coord);
+                let (origin_x, origin_y) = coord.origin(chunk.size);
+                let (gx, gy) = (x - origin_x, y - origin_y);
+                assert!(chunk
+                    .cities
+                    .iter()
+                    .any(|c| (c.grid_x, c.grid_y) == (gx, gy)));
+                assert!(!chunk.terrain.get(gx, gy).is_water());
+            }
+        }
+    }
+}
+
+#[test]
+fn memoised_regions_match_fresh_ones() {
+    let mut generator = common::layered_generator(4);
+    let hydrology = Hydrology::new(4, generator.chunk_size());
+    let fresh = |coord, spacing| RegionData::generate(4, coord, &hydrology, 64, spacing);
+    let coords = [(0, 0), (-1, 0), (0, -1), (0, 0), (3, -2)].map(|(x, y)| RegionCoord::new(x, y));
+    for coord in coords {
+        assert_eq!(generator.region_data(coord), fresh(coord, 0.0));
+    }
+
+    // Hub spacing follows a placement change
+    let wide = PlacementStrategy::PoissonDisc { min_distance: 60.0 };
+    generator.set_placement(wide).unwrap();
+    for coord in coords {
+        assert_eq!(generator.region_data(coord), fresh(coord, 60.0));
+    }
+}
@@ -0,0 +1,116 @@
+//! Voronoi territories, nations and borders across chunks.
+
+mod common;
+
+use opengrid_world::territory::max_territory_radius;
+use opengrid_world::{
+    cities_in_rect, territories_for_chunk, Border, ChunkCoord, Nations, Territories,
+};
+
+const SEED: u64 = 606;
+
+/// City owning a world cell, or None
+fn owner(territories: &Territories, x: i32, y: i32) -> Option<(i32, i32)> {
+    let (gx, gy) = (x - territories.coord.x * 64, y - territories.coord.y * 64);
+    territories.owner_at(gx, gy).map(|o| o.city)
+}
+
+/// Whether a merged border run covers the unit edge `from`-`to`
+fn covers(borders: &[Border], from: (i32, i32), to: (i32, i32)) -> Option<bool> {
+    borders
+        .iter()
+        .find(|b| {
+            if from.0 == to.0 {
+                b.from.0 == from.0 && b.to.0 == from.0 && b.from.1 <= from.1 && to.1 <= b.to.1
+            } else {
+                b.from.1 == from.1 && b.to.1 == from.1 && b.from.0 <= from.0 && to.0 <= b.to.0
+            }
+        })
+        .map(|b| b.national)
+}
+
+#[test]
+fn cells_belong_to_their_nearest_city() {
+    let mut cache = common::layered_cache(SEED);
+    let coord = ChunkCoord::new(1, -1);
+    let territories = territories_for_chunk(&mut cache, coord, SEED);
+    let cities = cities_in_rect(&mut cache, -64.0, -192.0, 191.0, 63.0).unwrap();
+    let nations = Nations::new(SEED);
+    let terrain = &cache.get(coord).unwrap().terrain;
+    let max_sq = max_territory_radius(64).pow(2);
+
+    let mut claimed = 0;
+    for gy in 0..64 {
+        for gx in 0..64 {
+            let (x, y) = (64 + gx, -64 + gy);
+            let nearest = cities
+                .iter()
+                .map(|c| {
+                    let d = ((c.x - x) as i64).pow(2) + ((c.y - y) as i64).pow(2);
+                    (d, (c.x, c.y))
+                })
+                .filter(|&(d, _)| d <= max_sq)
+                .min()
+                .map(|(_, city)| city);
+            let expected = if terrain.get(gx, gy).is_water() {
+                None
+            } else {
+                nearest
+            };
+            assert_eq!(owner(&territories, x, y), expected, "cell ({}, {})", x, y);
+            claimed += expected.is_some() as usize;
+        }
+    }
+    assert!(claimed > 0);
+
+    for o in &territories.owners {
+        assert_eq!(o.nation, nations.nation_of(o.city.0, o.city.1));
+    }
+}
+
+#[test]
+fn neighbouring_chunks_agree_on_shared_borders() {
+    let nation = |t: &Territories, x: i32, y: i32| {
+        let (gx, gy) = (x - t.coord.x * 64, y - t.coord.y * 64);
+        t.owner_at(gx, gy).map(|o| o.nation)
+    };
+    // Border expected on the edge between two cells, and whether it is national
+    let expected = |a: &Territories, p: (i32, i32), b: &Territories, q: (i32, i32)| match (
+        owner(a, p.0, p.1),
+        owner(b, q.0, q.1),
+    ) {
+        (Some(x), Some(y)) if x != y => Some(nation(a, p.0, p.1) != nation(b, q.0, q.1)),
+        _ => None,
+    };
+
+    let mut seam_borders = 0;
+    for (cx, cy) in [(0, 0), (2, -1), (-3, 2), (5, 5)] {
+        let (left, right) = (ChunkCoord::new(cx, cy), ChunkCoord::new(cx + 1, cy));
+        let a = territories_for_chunk(&mut common::layered_cache(SEED), left, SEED);
+        let b = territories_for_chunk(&mut common::layered_cache(SEED), right, SEED);
+        let (ox, oy) = (cx * 64, cy * 64);
+
+        // The left chunk owns the seam and must match the right chunk's cells
+        let seam = ox + 64;
+        for y in oy..oy + 64 {
+            let edge = ((seam, y), (seam, y + 1));
+            let border = expected(&a, (seam - 1, y), &b, (seam, y));
+            assert_eq!(covers(&a.borders, edge.0, edge.1), border, "seam row {}", y);
+            assert_eq!(covers(&b.borders, edge.0, edge.1), None);
+            seam_borders += border.is_some() as usize;
+        }
+
+        // Inside a chunk, borders run exactly where claimed owners change
+        for i in 0..64 {
+            for j in 1..64 {
+                let (x, y) = (ox + j, oy + i);
+                let border = expected(&a, (x - 1, y), &a, (x, y));
+                assert_eq!(covers(&a.borders, (x, y), (x, y + 1)), border);
+                let (x, y) = (ox + i, oy + j);
+                let border = expected(&a, (x, y - 1), &a, (x, y));
+                assert_eq!(covers(&a.borders, (x, y), (x + 1, y)), border);
+            }
+        }
+    }
+    assert!(seam_borders > 0);
+}
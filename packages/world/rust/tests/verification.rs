@@ -0,0 +1,96 @@
+//! City lookup and verification.
+
+use opengrid_world::{
+    city_at, city_near, generate, verify_cities, ChunkCache, ChunkCoord, CityAttributes,
+    FixtureGenerator, WorldGenerator,
+};
+
+fn fixture() -> ChunkCache<FixtureGenerator> {
+    let generator = FixtureGenerator::new(64)
+        .with_city(64, 0, 11)
+        .with_city(-1, -65, 22);
+    ChunkCache::with_generator(generator)
+}
+
+#[test]
+fn exact_lookup_returns_the_full_record() {
+    let mut cache = fixture();
+    let city = city_at(&mut cache, -1, -65).unwrap();
+    assert_eq!(
+        (city.chunk, city.grid_x, city.grid_y),
+        (ChunkCoord::new(-1, -2), 63, 63)
+    );
+
+    let record = city.record();
+    assert_eq!((record.chunk_x, record.chunk_y), (-1, -2));
+    assert_eq!((record.world_x, record.world_y, record.seed), (-1, -65, 22));
+    assert_eq!(record.attributes, CityAttributes::from_seed(22));
+
+    assert!(city_at(&mut cache, -1, -64).is_none());
+}
+
+#[test]
+fn tolerant_lookup_crosses_chunk_borders() {
+    let mut cache = fixture();
+    // The point lies in chunk (0, 0), the city in chunk (1, 0)
+    let city = city_near(&mut cache, 63.995, 0.0, 0.01).unwrap().unwrap();
+    assert_eq!((city.x, city.y, city.seed), (64, 0, 11));
+    assert!(city_near(&mut cache, 63.9, 0.0, 0.01).unwrap().is_none());
+    assert!(city_near(&mut cache, 63.0, 0.0, 1.0).unwrap().is_some());
+}
+
+#[test]
+fn batch_checks_position_and_seed() {
+    let mut cache = fixture();
+    let claims = [(64, 0, 11), (64, 0, 12), (-1, -65, 22), (0, 0, 11)];
+    assert_eq!(
+        verify_cities(&mut cache, &claims),
+        [true, false, true, false]
+    );
+}
+
+#[test]
+fn world_generator_verifies_tours() {
+    let chunk = generate(4242, ChunkCoord::new(-1, 2));
+    let city = &chunk.cities[0];
+    let (x, y) = city.world_position(&chunk.coord, chunk.size);
+
+    let mut world = WorldGenerator::new(4242);
+    assert!(world.verify_city(x, y));
+    // The tolerance is per axis: a diagonal offset beyond 0.01 still matches
+    assert!(world.verify_city(x + 0.009, y - 0.009));
+    assert!(world.verify_city(x - 0.009, y + 0.009));
+    assert!(!world.verify_city(x + 0.011, y));
+    assert!(!world.verify_city(x, y - 0.02));
+
+    let record = world.lookup_city_at(x as i32, y as i32).unwrap();
+    assert_eq!((record.chunk_x, record.chunk_y), (-1, 2));
+    assert_eq!((record.grid_x, record.grid_y), (city.grid_x, city.grid_y));
+    assert_eq!(record.attributes, city.attributes());
+    assert_eq!(world.lookup_city(x + 0.3, y, 0.5).unwrap(), Some(record));
+
+    let seed = city.seed as f64;
+    let claims = vec![x, y, seed, x, y, seed + 1.0, x + 0.5, y, seed, x, y, -1.0];
+    assert_eq!(world.verify_cities(claims).unwrap(), [1, 0, 0, 0]);
+}
+
+#[test]
+fn single_checks_cross_chunk_borders() {
+    // A city on the first column of its chunk, checked from the chunk to the west
+    let (chunk, city) = (0..64)
+        .map(|cx| generate(4242, ChunkCoord::new(cx, 0)))
+        .find_map(|chunk| {
+            let city = chunk.cities.iter().find(|c| c.grid_x == 0)?.clone();
+            Some((chunk, city))
+        })
+        .unwrap();
+    let (x, y) = city.world_position(&chunk.coord, chunk.size);
+    assert_eq!(
+        (x - 0.005).div_euclid(chunk.size as f64) as i32,
+        chunk.coord.x - 1
+    );
+
+    let mut world = WorldGenerator::new(4242);
+    assert!(world.verify_city(x - 0.005, y));
+    assert!(!world.verify_city(x - 0.5, y));
+}
@@ -1,6 +1,6 @@
 use wasm_bindgen::prelude::*;
 
-pub use opengrid_renderer::{WorldRenderer, CityInfo, RenderStats};
+pub use opengrid_renderer::{WorldRenderer, CityInfo, RenderStats, Theme};
 
 #[wasm_bindgen(start)]
 pub fn init() {